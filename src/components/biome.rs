@@ -0,0 +1,32 @@
+use serde::Deserialize;
+
+/// Kind of region a chunk belongs to. Decides how the chunk is populated;
+/// the numbers for each kind come from the `biomes` section of the game config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum Biome {
+    /// A few drifting asteroids, like space used to be everywhere.
+    Sparse,
+    /// Many slow asteroids packed close together.
+    Field,
+    /// Next to nothing.
+    Void,
+    IceBelt,
+    /// Small, fast asteroids all heading the same way.
+    DebrisStream,
+    GravityWell,
+    EnemyTerritory,
+}
+
+impl Biome {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Biome::Sparse => "open space",
+            Biome::Field => "asteroid field",
+            Biome::Void => "void",
+            Biome::IceBelt => "ice belt",
+            Biome::DebrisStream => "debris stream",
+            Biome::GravityWell => "gravity well",
+            Biome::EnemyTerritory => "enemy territory",
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use bevy::prelude::Entity;
+use serde::Deserialize;
+
+/// Scripted bosses met at milestone distances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum BossKind {
+    /// A long chain of rocks that chases the player head first.
+    Worm,
+    /// A slow capital ship that keeps its distance and launches fighters.
+    Carrier,
+    /// A rock torso that spins its jointed arms around.
+    Golem,
+}
+
+impl BossKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BossKind::Worm => "Asteroid Worm",
+            BossKind::Carrier => "Carrier",
+            BossKind::Golem => "Rock Golem",
+        }
+    }
+}
+
+/// The core of a boss. Its other parts are jointed to it, and point back at it with `BossPart`.
+pub struct Boss {
+    pub kind: BossKind,
+    /// Sturdiness of all weak points together when the boss spawned.
+    pub max_health: f32,
+    /// Fraction of the weak points' sturdiness left.
+    pub health: f32,
+    pub phase: u32,
+    /// Seconds until the next attack.
+    pub cooldown: f32,
+}
+
+impl Boss {
+    /// Bosses get more aggressive as their health runs down, one phase per third.
+    pub const PHASES: u32 = 3;
+
+    pub fn new(kind: BossKind, max_health: f32) -> Self {
+        Self {
+            kind,
+            max_health,
+            health: 1.0,
+            phase: 1,
+            cooldown: 0.0,
+        }
+    }
+
+    pub fn phase_for(health: f32) -> u32 {
+        let lost = ((1.0 - health) * Self::PHASES as f32) as u32;
+        (lost + 1).min(Self::PHASES)
+    }
+}
+
+/// A body making up part of a boss. The boss is beaten once all its weak points are destroyed.
+pub struct BossPart {
+    pub boss: Entity,
+    pub weak_point: bool,
+}
+
+/// An enemy launched by a carrier, which counts towards how many it can have out at once.
+pub struct Fighter {
+    pub carrier: Entity,
+}
+
+/// Milestones passed this run, each of which brings a boss.
+#[derive(Default)]
+pub struct BossEncounters(pub u32);
+
+/// Every node of the boss health bar, shown while a boss is close.
+pub struct BossBar;
+
+pub struct BossBarFill;
+
+pub struct BossBarText;
@@ -0,0 +1,24 @@
+use bevy::math::Vec2;
+
+/// State of the camera that follows the player. Lives on the 2D camera entity.
+pub struct CameraController {
+    /// Where the camera is heading, before shake is added.
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub zoom: f32,
+    pub zoom_velocity: f32,
+    /// From 0 to 1. Shake grows with the square of this, so small bumps barely register.
+    pub trauma: f32,
+}
+
+impl Default for CameraController {
+    fn default() -> Self {
+        Self {
+            position: Vec2::ZERO,
+            velocity: Vec2::ZERO,
+            zoom: 1.0,
+            zoom_velocity: 0.0,
+            trauma: 0.0,
+        }
+    }
+}
@@ -1,8 +1,6 @@
 use bevy::{math::Vec2, utils::HashSet};
 use itertools::*;
-use rand::{prelude::ThreadRng, Rng};
-
-const CHUNK_SIZE: f32 = 600.0;
+use rand::Rng;
 
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub struct Chunk(i32, i32);
@@ -15,30 +13,45 @@ impl Chunk {
         Vec2::new(self.0 as f32, self.1 as f32)
     }
 
-    pub fn top_right(&self) -> Vec2 {
-        self.bottom_left() + Vec2::new(CHUNK_SIZE, CHUNK_SIZE)
+    pub fn top_right(&self, size: f32) -> Vec2 {
+        self.bottom_left() + Vec2::new(size, size)
     }
 
-    pub fn random_point_inside(&self, rng: &mut ThreadRng) -> Vec2 {
+    pub fn random_point_inside(&self, rng: &mut impl Rng, size: f32) -> Vec2 {
         let lower = self.bottom_left();
-        let upper = self.top_right();
+        let upper = self.top_right(size);
         Vec2::new(
             rng.gen_range(lower.x..upper.x),
             rng.gen_range(lower.y..upper.y),
         )
     }
 
-    pub fn containing_point(pt: &Vec2) -> Self {
-        let closest_boundary = |x: f32| x - (x % CHUNK_SIZE);
+    /// Seed for this chunk's contents, so that a chunk generates the same way for a given world seed.
+    pub fn seed(&self, world_seed: u64) -> u64 {
+        let x = self.0 as u32 as u64;
+        let y = self.1 as u32 as u64;
+        world_seed ^ x.wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ y.wrapping_mul(0xC2B2_AE3D_27D4_EB4F)
+    }
+
+    /// Position of the chunk counted in chunks rather than pixels.
+    pub fn grid_coords(&self, size: f32) -> (i32, i32) {
+        (
+            (self.0 as f32 / size).round() as i32,
+            (self.1 as f32 / size).round() as i32,
+        )
+    }
+
+    pub fn containing_point(pt: &Vec2, size: f32) -> Self {
+        let closest_boundary = |x: f32| x - (x % size);
         Self::new(closest_boundary(pt.x), closest_boundary(pt.y))
     }
 
-    pub fn surrounding_chunks(&self) -> Vec<Self> {
+    pub fn surrounding_chunks(&self, size: f32) -> Vec<Self> {
         let as_vec = Vec2::new(self.0 as f32, self.1 as f32);
         [-2.0, -1.0, 0.0, 1.0]
             .iter()
             .cartesian_product([-2.0, -1.0, 0.0, 1.0])
-            .map(|(dx, dy)| (dx * CHUNK_SIZE, dy * CHUNK_SIZE))
+            .map(|(dx, dy)| (dx * size, dy * size))
             .map(|(dx, dy)| Chunk::new(as_vec.x + dx, as_vec.y + dy))
             .collect::<Vec<_>>()
     }
@@ -54,3 +67,12 @@ impl From<Vec2> for Chunk {
 /// A HashSet is defined by
 #[derive(Default)]
 pub struct SpawnedChunks(pub HashSet<Chunk>);
+
+/// Seed for procedural generation. Chunks are generated from this and their coordinates.
+pub struct WorldSeed(pub u64);
+
+impl Default for WorldSeed {
+    fn default() -> Self {
+        Self(rand::random())
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+
+pub type CommandResult = Result<String, String>;
+
+type CommandHandler = Box<dyn Fn(&mut World, &[&str]) -> CommandResult + Send + Sync>;
+
+pub struct ConsoleCommand {
+    pub help: &'static str,
+    handler: CommandHandler,
+}
+
+/// Commands the console can run, keyed by name.
+/// Subsystems add their own with `AppBuilder::add_console_command`.
+#[derive(Default)]
+pub struct CommandRegistry {
+    commands: BTreeMap<&'static str, ConsoleCommand>,
+}
+
+impl CommandRegistry {
+    pub fn register(
+        &mut self,
+        name: &'static str,
+        help: &'static str,
+        handler: impl Fn(&mut World, &[&str]) -> CommandResult + Send + Sync + 'static,
+    ) {
+        self.commands.insert(
+            name,
+            ConsoleCommand {
+                help,
+                handler: Box::new(handler),
+            },
+        );
+    }
+
+    pub fn run(&self, world: &mut World, line: &str) -> CommandResult {
+        let mut words = line.split_whitespace();
+        let name = match words.next() {
+            Some(name) => name,
+            None => return Ok(String::new()),
+        };
+        let args = words.collect::<Vec<_>>();
+
+        if name == "help" {
+            return Ok(self
+                .commands
+                .iter()
+                .map(|(name, command)| format!("{}: {}", name, command.help))
+                .collect::<Vec<_>>()
+                .join("\n"));
+        }
+        match self.commands.get(name) {
+            Some(command) => (command.handler)(world, &args),
+            None => Err(format!("unknown command `{}`, try `help`", name)),
+        }
+    }
+}
+
+pub trait ConsoleCommandsExt {
+    fn add_console_command(
+        &mut self,
+        name: &'static str,
+        help: &'static str,
+        handler: impl Fn(&mut World, &[&str]) -> CommandResult + Send + Sync + 'static,
+    ) -> &mut Self;
+}
+
+impl ConsoleCommandsExt for AppBuilder {
+    fn add_console_command(
+        &mut self,
+        name: &'static str,
+        help: &'static str,
+        handler: impl Fn(&mut World, &[&str]) -> CommandResult + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_insert_with(CommandRegistry::default)
+            .register(name, help, handler);
+        self
+    }
+}
+
+/// State of the developer console. Toggled with the backquote key.
+#[derive(Default)]
+pub struct Console {
+    pub open: bool,
+    pub input: String,
+    /// Lines typed by the user and waiting to be run.
+    pub submitted: Vec<String>,
+    /// Commands and their output, oldest first.
+    pub history: Vec<String>,
+}
+
+pub struct ConsoleText;
+
+/// When enabled, the player's ship doesn't lose sturdiness.
+#[derive(Default)]
+pub struct GodMode(pub bool);
+
+/// Multiplier on how fast the physics simulation runs.
+pub struct TimeScale(pub f32);
+
+impl TimeScale {
+    /// Longest stretch of real time a single physics step covers, so a slow frame can't launch
+    /// bodies through each other.
+    pub const MAX_FRAME_STEP: f32 = 1.0 / 60.0;
+
+    /// Simulated seconds to advance for a frame that took `delta` seconds.
+    pub fn physics_step(&self, delta: f32) -> f32 {
+        delta.min(Self::MAX_FRAME_STEP) * self.0
+    }
+}
+
+impl Default for TimeScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// Parse a single numeric argument for a console command.
+pub fn parse_arg<T: std::str::FromStr>(
+    args: &[&str],
+    index: usize,
+    name: &str,
+) -> Result<T, String> {
+    args.get(index)
+        .ok_or_else(|| format!("missing argument <{}>", name))?
+        .parse()
+        .map_err(|_| format!("invalid value for <{}>", name))
+}
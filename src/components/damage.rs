@@ -0,0 +1,225 @@
+use bevy::{
+    prelude::{Color, Entity, Vec2},
+    utils::HashMap,
+};
+use bevy_rapier2d::prelude::InteractionGroups;
+
+/// Broad class of a physical entity.
+/// Used to pick the collider's collision and solver groups, and to look up damage rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    Ship,
+    Bullet,
+    Asteroid,
+    Enemy,
+    Pickup,
+}
+
+impl Category {
+    pub const ALL: [Category; 5] = [
+        Category::Ship,
+        Category::Bullet,
+        Category::Asteroid,
+        Category::Enemy,
+        Category::Pickup,
+    ];
+
+    fn bit(&self) -> u32 {
+        1 << (*self as u32)
+    }
+
+    /// Whether colliders of these two categories should generate contacts at all.
+    fn detects(&self, other: &Category) -> bool {
+        use Category::*;
+        match (self, other) {
+            (Bullet, Bullet) => false,
+            (Pickup, Ship) | (Ship, Pickup) => true,
+            (Pickup, _) | (_, Pickup) => false,
+            _ => true,
+        }
+    }
+
+    /// Whether contacts between these two categories should push the bodies apart.
+    fn solves(&self, other: &Category) -> bool {
+        use Category::*;
+        match (self, other) {
+            (Pickup, _) | (_, Pickup) => false,
+            _ => self.detects(other),
+        }
+    }
+
+    fn mask(&self, pred: impl Fn(&Category) -> bool) -> u32 {
+        Category::ALL
+            .iter()
+            .filter(|c| pred(c))
+            .fold(0, |mask, c| mask | c.bit())
+    }
+
+    pub fn collision_groups(&self) -> InteractionGroups {
+        InteractionGroups::new(self.bit(), self.mask(|c| self.detects(c)))
+    }
+
+    pub fn solver_groups(&self) -> InteractionGroups {
+        InteractionGroups::new(self.bit(), self.mask(|c| self.solves(c)))
+    }
+}
+
+/// The entity that fired a projectile, and what kind of entity it was.
+#[derive(Debug, Clone, Copy)]
+pub struct Owner {
+    pub entity: Entity,
+    pub category: Category,
+}
+
+/// Number of additional bodies a projectile can pass through before it is destroyed.
+pub struct Piercing(pub u32);
+
+/// A collision, as remembered by a body it hit.
+#[derive(Debug, Clone, Copy)]
+pub struct Impact {
+    pub position: Vec2,
+    /// Collision energy absorbed by both bodies.
+    pub energy: f32,
+}
+
+/// The last collision a body was in, so it can break along the line of the blow.
+#[derive(Debug, Default)]
+pub struct LastImpact(pub Option<Impact>);
+
+/// Table of damage multipliers keyed by (source, target) category.
+/// Pairs missing from the table deal unscaled damage.
+pub struct DamageRules {
+    multipliers: HashMap<(Category, Category), f32>,
+    /// Whether projectiles can damage their owner, or entities of the owner's category.
+    pub friendly_fire: bool,
+}
+
+impl DamageRules {
+    pub fn multiplier(&self, source: Category, target: Category) -> f32 {
+        *self.multipliers.get(&(source, target)).unwrap_or(&1.0)
+    }
+
+    pub fn set(&mut self, source: Category, target: Category, multiplier: f32) -> &mut Self {
+        self.multipliers.insert((source, target), multiplier);
+        self
+    }
+
+    /// Whether a projectile with the given owner should damage `target`.
+    pub fn allows(&self, owner: Option<&Owner>, target: Entity, target_category: Category) -> bool {
+        match owner {
+            Some(owner) if !self.friendly_fire => {
+                owner.entity != target && owner.category != target_category
+            }
+            _ => true,
+        }
+    }
+}
+
+impl Default for DamageRules {
+    fn default() -> Self {
+        use Category::*;
+        let mut rules = Self {
+            multipliers: HashMap::default(),
+            friendly_fire: false,
+        };
+        rules
+            .set(Asteroid, Asteroid, 0.25)
+            .set(Bullet, Asteroid, 4.0)
+            .set(Bullet, Enemy, 4.0)
+            .set(Asteroid, Bullet, 0.0)
+            .set(Enemy, Bullet, 0.0)
+            .set(Ship, Bullet, 0.0);
+        for category in Category::ALL.iter() {
+            rules
+                .set(Pickup, *category, 0.0)
+                .set(*category, Pickup, 0.0);
+        }
+        rules
+    }
+}
+
+/// How badly damaged a body is, judged by the fraction of its sturdiness it has left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Condition {
+    Intact,
+    Damaged,
+    Critical,
+}
+
+impl Condition {
+    pub fn from_fraction(fraction: f32) -> Self {
+        if fraction <= 0.25 {
+            Condition::Critical
+        } else if fraction <= 0.5 {
+            Condition::Damaged
+        } else {
+            Condition::Intact
+        }
+    }
+
+    /// Outline color of a ship in this condition.
+    pub fn color(&self) -> Color {
+        match self {
+            Condition::Intact => Color::WHITE,
+            Condition::Damaged => Color::ORANGE,
+            Condition::Critical => Color::ORANGE_RED,
+        }
+    }
+}
+
+/// Crack lines drawn over a damaged asteroid, and how many of them there are.
+pub struct Cracks {
+    pub count: u32,
+}
+
+pub struct HullWarningText;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owner(id: u32, category: Category) -> Owner {
+        Owner {
+            entity: Entity::new(id),
+            category,
+        }
+    }
+
+    #[test]
+    fn missing_pairs_deal_unscaled_damage() {
+        let rules = DamageRules::default();
+        assert_eq!(rules.multiplier(Category::Ship, Category::Asteroid), 1.0);
+        assert_eq!(rules.multiplier(Category::Bullet, Category::Asteroid), 4.0);
+        assert_eq!(rules.multiplier(Category::Pickup, Category::Ship), 0.0);
+        assert_eq!(rules.multiplier(Category::Ship, Category::Pickup), 0.0);
+    }
+
+    #[test]
+    fn set_overrides_a_pair() {
+        let mut rules = DamageRules::default();
+        rules.set(Category::Ship, Category::Enemy, 2.5);
+        assert_eq!(rules.multiplier(Category::Ship, Category::Enemy), 2.5);
+        assert_eq!(rules.multiplier(Category::Enemy, Category::Ship), 1.0);
+    }
+
+    #[test]
+    fn projectiles_spare_their_own_side() {
+        let rules = DamageRules::default();
+        let shooter = owner(1, Category::Ship);
+        assert!(!rules.allows(Some(&shooter), Entity::new(1), Category::Ship));
+        assert!(!rules.allows(Some(&shooter), Entity::new(2), Category::Ship));
+        assert!(rules.allows(Some(&shooter), Entity::new(2), Category::Enemy));
+        assert!(rules.allows(None, Entity::new(1), Category::Ship));
+    }
+
+    #[test]
+    fn friendly_fire_hits_everyone() {
+        let rules = DamageRules {
+            friendly_fire: true,
+            ..DamageRules::default()
+        };
+        let shooter = owner(1, Category::Enemy);
+        assert!(rules.allows(Some(&shooter), Entity::new(1), Category::Enemy));
+        assert!(rules.allows(Some(&shooter), Entity::new(2), Category::Enemy));
+    }
+}
@@ -0,0 +1,11 @@
+/// Whether the debug overlay is shown. Toggled with F3.
+#[derive(Default)]
+pub struct DebugOverlay {
+    pub enabled: bool,
+}
+
+/// Text block with frame, entity and player stats.
+pub struct DebugText;
+
+/// Overlay geometry and labels, respawned every frame while the overlay is enabled.
+pub struct DebugShape;
@@ -0,0 +1,58 @@
+use serde::Deserialize;
+
+/// How hard the game gets. Each preset multiplies every difficulty curve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum DifficultyPreset {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl DifficultyPreset {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DifficultyPreset::Easy => "easy",
+            DifficultyPreset::Normal => "normal",
+            DifficultyPreset::Hard => "hard",
+        }
+    }
+
+    pub fn harder(&self) -> Self {
+        match self {
+            DifficultyPreset::Easy => DifficultyPreset::Normal,
+            _ => DifficultyPreset::Hard,
+        }
+    }
+
+    pub fn easier(&self) -> Self {
+        match self {
+            DifficultyPreset::Hard => DifficultyPreset::Normal,
+            _ => DifficultyPreset::Easy,
+        }
+    }
+}
+
+/// Difficulty of the current run.
+pub struct Difficulty {
+    pub preset: DifficultyPreset,
+    /// When the run started, in seconds since startup.
+    pub run_started: f64,
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Self {
+            preset: DifficultyPreset::Normal,
+            run_started: 0.0,
+        }
+    }
+}
+
+/// Multipliers on the world's base values at some difficulty.
+#[derive(Debug, Clone, Copy)]
+pub struct DifficultyScaling {
+    pub asteroid_count: f32,
+    pub asteroid_speed: f32,
+    pub enemy_chance: f32,
+    pub loot_quality: f32,
+}
@@ -0,0 +1,84 @@
+use bevy::utils::HashMap;
+use serde::Deserialize;
+
+/// What mined asteroids break down into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum Ore {
+    Iron,
+    Ice,
+    Crystal,
+}
+
+impl Ore {
+    pub const ALL: [Ore; 3] = [Ore::Iron, Ore::Ice, Ore::Crystal];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Ore::Iron => "iron",
+            Ore::Ice => "ice",
+            Ore::Crystal => "crystal",
+        }
+    }
+}
+
+/// Ore carried by a ship, up to its capacity.
+pub struct CargoHold {
+    pub capacity: f32,
+    pub ore: HashMap<Ore, f32>,
+}
+
+impl CargoHold {
+    pub fn new(capacity: f32) -> Self {
+        Self {
+            capacity,
+            ore: Default::default(),
+        }
+    }
+
+    pub fn used(&self) -> f32 {
+        self.ore.values().sum()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.used() >= self.capacity
+    }
+
+    /// Load as much of `amount` as fits, and return how much that was.
+    pub fn add(&mut self, ore: Ore, amount: f32) -> f32 {
+        let added = amount.min(self.capacity - self.used()).max(0.0);
+        if added > 0.0 {
+            *self.ore.entry(ore).or_insert(0.0) += added;
+        }
+        added
+    }
+}
+
+/// Improvements bought at stations. They last as long as the ship does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Upgrade {
+    Engines,
+    Hull,
+    Cargo,
+}
+
+impl Upgrade {
+    pub const ALL: [Upgrade; 3] = [Upgrade::Engines, Upgrade::Hull, Upgrade::Cargo];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Upgrade::Engines => "engines",
+            Upgrade::Hull => "hull",
+            Upgrade::Cargo => "cargo hold",
+        }
+    }
+}
+
+/// Number of times each upgrade was bought for a ship.
+#[derive(Default)]
+pub struct Upgrades(pub HashMap<Upgrade, u32>);
+
+impl Upgrades {
+    pub fn level(&self, upgrade: Upgrade) -> u32 {
+        self.0.get(&upgrade).copied().unwrap_or(0)
+    }
+}
@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+
+use super::{
+    damage::{Category, Impact},
+    material::Material,
+};
+
+/// Sent for every collision that was processed by the damage model.
+pub struct ImpactEvent {
+    /// Where the two bodies touched.
+    pub position: Vec2,
+    /// Collision energy absorbed by both bodies, before damage rules are applied.
+    pub energy: f32,
+}
+
+/// Sent when an entity runs out of sturdiness and is despawned.
+pub struct DestroyedEvent {
+    pub entity: Entity,
+    pub category: Category,
+    pub position: Vec2,
+    /// In radians, counterclockwise.
+    pub rotation: f32,
+    pub velocity: Vec2,
+    pub angular_velocity: f32,
+    pub mass: f32,
+    pub material: Option<Material>,
+    /// What an asteroid looked like, in its local space. Empty for everything else.
+    pub outline: Vec<Vec<(f32, f32)>>,
+    /// The last collision it was in, if it kept track.
+    pub impact: Option<Impact>,
+    /// Whether it was a piece of something already destroyed, which earns nothing more.
+    pub fragment: bool,
+}
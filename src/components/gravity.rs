@@ -0,0 +1,42 @@
+use bevy::math::Vec2;
+
+use crate::config::WellConfig;
+
+/// Pulls nearby bodies towards itself with inverse-square gravity.
+#[derive(Debug, Clone)]
+pub struct GravityWell {
+    /// Gravitational parameter: acceleration at a distance of one pixel.
+    pub strength: f32,
+    /// Radius of the body. The pull stops growing once inside it.
+    pub radius: f32,
+    /// Bodies further away than this aren't pulled at all.
+    pub range: f32,
+    /// Whether anything reaching the edge is destroyed, like a black hole.
+    pub swallows: bool,
+}
+
+impl GravityWell {
+    pub fn new(config: &WellConfig, swallows: bool) -> Self {
+        Self {
+            strength: config.strength,
+            radius: config.radius,
+            range: config.range,
+            swallows,
+        }
+    }
+
+    /// Acceleration felt by a body at `offset` from the well, or `None` if it is out of range.
+    pub fn acceleration(&self, offset: Vec2) -> Option<Vec2> {
+        let distance_squared = offset.length_squared();
+        if distance_squared > self.range * self.range || distance_squared == 0.0 {
+            return None;
+        }
+        let pull = self.strength / distance_squared.max(self.radius * self.radius);
+        Some(-offset / distance_squared.sqrt() * pull)
+    }
+
+    /// Speed of a circular orbit at `distance` from the well.
+    pub fn orbital_speed(&self, distance: f32) -> f32 {
+        (self.strength / distance).sqrt()
+    }
+}
@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+
+/// Which stat a HUD bar shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HudBar {
+    Hull,
+    Fuel,
+}
+
+/// The filled part of a HUD bar. Its width is set from the stat it shows.
+pub struct HudBarFill(pub HudBar);
+
+/// Readouts for score, speed, heading, weapon, lives and sector.
+pub struct HudText;
+
+pub struct Minimap;
+
+/// A dot on the minimap. A fixed number are spawned, and hidden when there's nothing to show.
+pub struct MinimapBlip;
+
+/// Ships the player has left in this run, including the one being flown.
+#[derive(Default)]
+pub struct Lives(pub u32);
+
+/// Colors used by the HUD, created once at startup.
+pub struct HudMaterials {
+    pub panel: Handle<ColorMaterial>,
+    pub hull: Handle<ColorMaterial>,
+    pub fuel: Handle<ColorMaterial>,
+    pub asteroid: Handle<ColorMaterial>,
+    pub enemy: Handle<ColorMaterial>,
+    pub pickup: Handle<ColorMaterial>,
+    pub player: Handle<ColorMaterial>,
+    pub boss: Handle<ColorMaterial>,
+}
+
+/// How soon an off-screen threat is due to hit the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Urgency {
+    Low,
+    Medium,
+    High,
+}
+
+impl Urgency {
+    pub const ALL: [Urgency; 3] = [Urgency::Low, Urgency::Medium, Urgency::High];
+
+    /// Indicators of each urgency that can be shown at once.
+    pub const CAPACITY: usize = 8;
+
+    /// Split the warning window into thirds, with the last third the most urgent.
+    pub fn from_time(time_to_impact: f32, warning_time: f32) -> Self {
+        let remaining = time_to_impact / warning_time;
+        if remaining < 1.0 / 3.0 {
+            Urgency::High
+        } else if remaining < 2.0 / 3.0 {
+            Urgency::Medium
+        } else {
+            Urgency::Low
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            Urgency::Low => Color::YELLOW,
+            Urgency::Medium => Color::ORANGE,
+            Urgency::High => Color::RED,
+        }
+    }
+}
+
+/// Arrow at the edge of the screen pointing at something on a collision course with the player.
+pub struct ThreatIndicator {
+    pub urgency: Urgency,
+}
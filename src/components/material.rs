@@ -0,0 +1,60 @@
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng};
+use serde::Deserialize;
+
+/// What a body is made of.
+/// Determines the collider's physical properties as well as how easily it is damaged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum Material {
+    Rock,
+    Ice,
+    Metal,
+    Crystal,
+}
+
+pub struct MaterialProps {
+    pub restitution: f32,
+    pub friction: f32,
+    pub density: f32,
+    /// Scales the collision energy absorbed by a body into sturdiness loss.
+    pub damage_coefficient: f32,
+}
+
+impl Material {
+    pub fn props(&self) -> MaterialProps {
+        match self {
+            Material::Rock => MaterialProps {
+                restitution: 0.9,
+                friction: 0.5,
+                density: 1.0,
+                damage_coefficient: 500.0_f32.recip(),
+            },
+            Material::Ice => MaterialProps {
+                restitution: 0.7,
+                friction: 0.05,
+                density: 0.7,
+                damage_coefficient: 300.0_f32.recip(),
+            },
+            Material::Metal => MaterialProps {
+                restitution: 0.95,
+                friction: 0.3,
+                density: 1.0,
+                damage_coefficient: 800.0_f32.recip(),
+            },
+            Material::Crystal => MaterialProps {
+                restitution: 0.85,
+                friction: 0.4,
+                density: 1.4,
+                damage_coefficient: 250.0_f32.recip(),
+            },
+        }
+    }
+
+    /// Pick a material for a cluster of asteroids, from materials paired with their relative odds.
+    /// Rock if there is nothing to pick from.
+    pub fn pick(rng: &mut impl Rng, weights: &[(Material, u32)]) -> Self {
+        match WeightedIndex::new(weights.iter().map(|(_, weight)| *weight)) {
+            Ok(index) => weights[index.sample(rng)].0,
+            Err(_) => Material::Rock,
+        }
+    }
+}
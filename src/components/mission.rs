@@ -0,0 +1,101 @@
+use bevy::{
+    prelude::{Entity, Vec2},
+    utils::HashSet,
+};
+use serde::Deserialize;
+
+/// Kinds of mission a sector can offer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum MissionKind {
+    Destroy,
+    Escort,
+    Reach,
+    Defend,
+    Boss,
+}
+
+impl MissionKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MissionKind::Destroy => "clear asteroids",
+            MissionKind::Escort => "escort the drone",
+            MissionKind::Reach => "reach the waypoint",
+            MissionKind::Defend => "defend the station",
+            MissionKind::Boss => "destroy the boss",
+        }
+    }
+}
+
+/// What is left to do for a mission, and the entities it depends on.
+#[derive(Debug, Clone, Copy)]
+pub enum Objective {
+    /// Destroy this many more asteroids.
+    Destroy { remaining: u32 },
+    /// Keep the drone in one piece until it reaches the waypoint.
+    Escort { drone: Entity },
+    /// Fly to the waypoint before the fuel runs out.
+    Reach,
+    /// Keep raiders from wearing down the station until the time runs out.
+    Defend {
+        station: Entity,
+        remaining: f32,
+        integrity: f32,
+        next_raid: f32,
+    },
+    /// Break this boss.
+    Boss { boss: Entity },
+}
+
+#[derive(Debug, Clone)]
+pub struct Mission {
+    pub kind: MissionKind,
+    pub objective: Objective,
+    /// Where the HUD points the player, if anywhere.
+    pub waypoint: Option<Vec2>,
+    pub credits: u32,
+    pub score: i32,
+}
+
+impl Mission {
+    /// One line of progress for the HUD.
+    pub fn describe(&self) -> String {
+        match self.objective {
+            Objective::Destroy { remaining } => {
+                format!("{} ({} left)", self.kind.name(), remaining)
+            }
+            Objective::Defend {
+                remaining,
+                integrity,
+                ..
+            } => format!(
+                "{} ({:.0}s, integrity {:.0})",
+                self.kind.name(),
+                remaining.ceil(),
+                integrity
+            ),
+            _ => self.kind.name().to_string(),
+        }
+    }
+}
+
+/// The player's current mission, and the sectors that have already handed one out.
+#[derive(Default)]
+pub struct Missions {
+    pub active: Option<Mission>,
+    pub offered: HashSet<(i32, i32)>,
+    /// Time after which the next mission can be offered.
+    pub next_offer: f64,
+    /// How the last mission ended, shown on the HUD until the given time.
+    pub notice: Option<(String, f64)>,
+}
+
+/// A slow cargo drone that follows its route while the player keeps it company.
+pub struct CargoDrone {
+    pub destination: Vec2,
+}
+
+/// Enemy ship that goes after this entity instead of the player.
+pub struct Hunting(pub Entity);
+
+/// Arrow pointing the player at the current mission's waypoint.
+pub struct WaypointMarker;
@@ -1,3 +1,21 @@
+pub mod biome;
+pub mod boss;
+pub mod camera;
 pub mod chunk;
+pub mod console;
+pub mod damage;
+pub mod debug;
+pub mod difficulty;
+pub mod economy;
+pub mod events;
+pub mod gravity;
+pub mod hud;
+pub mod material;
+pub mod mission;
+pub mod particle;
+pub mod pickup;
+pub mod shield;
 pub mod ship;
+pub mod starfield;
+pub mod station;
 pub mod types;
@@ -0,0 +1,108 @@
+use bevy::{prelude::*, utils::HashMap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParticleKind {
+    Exhaust,
+    Spark,
+    Debris,
+    Explosion,
+}
+
+impl ParticleKind {
+    pub const ALL: [ParticleKind; 4] = [
+        ParticleKind::Exhaust,
+        ParticleKind::Spark,
+        ParticleKind::Debris,
+        ParticleKind::Explosion,
+    ];
+
+    /// Maximum number of live particles of this kind.
+    pub fn capacity(&self) -> usize {
+        match self {
+            ParticleKind::Exhaust => 64,
+            ParticleKind::Spark => 64,
+            ParticleKind::Debris => 96,
+            ParticleKind::Explosion => 96,
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            ParticleKind::Exhaust => Color::ORANGE,
+            ParticleKind::Spark => Color::YELLOW,
+            ParticleKind::Debris => Color::GRAY,
+            ParticleKind::Explosion => Color::ORANGE_RED,
+        }
+    }
+
+    pub fn shape(&self) -> Vec<(f32, f32)> {
+        match self {
+            ParticleKind::Debris => vec![(-2.0, -1.5), (0.0, 2.0), (2.0, -1.0)],
+            _ => vec![(-1.0, -1.0), (-1.0, 1.0), (1.0, 1.0), (1.0, -1.0)],
+        }
+    }
+
+    /// Range of lifetimes, in seconds.
+    pub fn lifetime(&self) -> (f32, f32) {
+        match self {
+            ParticleKind::Exhaust => (0.15, 0.35),
+            ParticleKind::Spark => (0.1, 0.3),
+            ParticleKind::Debris => (0.6, 1.4),
+            ParticleKind::Explosion => (0.4, 1.0),
+        }
+    }
+}
+
+pub struct Particle {
+    pub kind: ParticleKind,
+    pub velocity: Vec2,
+    pub age: f32,
+    pub lifetime: f32,
+}
+
+/// Preallocated particle entities, so effects never spawn or despawn entities.
+/// Emitting when a kind has no free particles left drops the particle.
+#[derive(Default)]
+pub struct ParticlePool {
+    pub free: HashMap<ParticleKind, Vec<Entity>>,
+}
+
+/// Continuously emits particles while `active`, e.g. engine exhaust.
+pub struct ParticleEmitter {
+    pub kind: ParticleKind,
+    pub active: bool,
+    /// Particles per second.
+    pub rate: f32,
+    /// Emission point and direction, relative to the emitting body.
+    pub offset: Vec2,
+    pub direction: Vec2,
+    pub speed: f32,
+    /// Maximum angle, in radians, that particles deviate from `direction`.
+    pub spread: f32,
+    pub accumulator: f32,
+}
+
+impl ParticleEmitter {
+    pub fn exhaust() -> Self {
+        Self {
+            kind: ParticleKind::Exhaust,
+            active: false,
+            rate: 60.0,
+            offset: Vec2::new(0.0, -14.0),
+            direction: Vec2::new(0.0, -1.0),
+            speed: 120.0,
+            spread: 0.3,
+            accumulator: 0.0,
+        }
+    }
+}
+
+/// One-off burst of particles, e.g. for an impact or explosion.
+pub struct ParticleBurst {
+    pub kind: ParticleKind,
+    pub position: Vec2,
+    /// Velocity inherited by every particle in the burst.
+    pub velocity: Vec2,
+    pub count: usize,
+    pub speed: f32,
+}
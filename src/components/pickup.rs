@@ -0,0 +1,85 @@
+use bevy::{prelude::Color, utils::HashMap};
+use serde::Deserialize;
+
+use super::economy::Ore;
+
+/// Temporary effects a pickup can give the player's ship.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum PowerUp {
+    /// Score from destroying things is doubled.
+    DoubleScore,
+    /// Engines push harder.
+    Overdrive,
+}
+
+impl PowerUp {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PowerUp::DoubleScore => "double score",
+            PowerUp::Overdrive => "overdrive",
+        }
+    }
+}
+
+/// What a pickup does when the player collects it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum PickupKind {
+    /// Refuels by the pickup's amount, up to the hull's capacity.
+    Fuel,
+    /// Restores the pickup's amount of sturdiness.
+    Repair,
+    /// Arms the ship with piercing shots, or adds to the ones it has.
+    Ammo,
+    Credits,
+    /// Lasts for the pickup's amount in seconds.
+    PowerUp(PowerUp),
+    /// Goes into the cargo hold, as far as there is room.
+    Ore(Ore),
+}
+
+impl PickupKind {
+    /// Kinds that can be named in console commands.
+    pub const NAMED: [(&'static str, PickupKind); 9] = [
+        ("fuel", PickupKind::Fuel),
+        ("repair", PickupKind::Repair),
+        ("ammo", PickupKind::Ammo),
+        ("credits", PickupKind::Credits),
+        ("double_score", PickupKind::PowerUp(PowerUp::DoubleScore)),
+        ("overdrive", PickupKind::PowerUp(PowerUp::Overdrive)),
+        ("iron", PickupKind::Ore(Ore::Iron)),
+        ("ice", PickupKind::Ore(Ore::Ice)),
+        ("crystal", PickupKind::Ore(Ore::Crystal)),
+    ];
+
+    pub fn color(&self) -> Color {
+        match self {
+            PickupKind::Fuel => Color::YELLOW,
+            PickupKind::Repair => Color::GREEN,
+            PickupKind::Ammo => Color::ORANGE,
+            PickupKind::Credits => Color::GOLD,
+            PickupKind::PowerUp(_) => Color::CYAN,
+            PickupKind::Ore(Ore::Iron) => Color::SILVER,
+            PickupKind::Ore(Ore::Ice) => Color::AZURE,
+            PickupKind::Ore(Ore::Crystal) => Color::VIOLET,
+        }
+    }
+}
+
+pub struct Pickup {
+    pub kind: PickupKind,
+    pub amount: f32,
+}
+
+/// Power-ups active on a ship, with the seconds they have left.
+#[derive(Default)]
+pub struct PowerUps(pub HashMap<PowerUp, f32>);
+
+impl PowerUps {
+    pub fn is_active(&self, power_up: PowerUp) -> bool {
+        self.0.contains_key(&power_up)
+    }
+}
+
+/// Currency collected over the current run.
+#[derive(Default)]
+pub struct Credits(pub u32);
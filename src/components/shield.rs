@@ -0,0 +1,104 @@
+use crate::config::ShieldConfig;
+
+/// Number of opacity levels the shield bubble is drawn at.
+/// The bubble's mesh is rebuilt whenever the level changes, so this is kept small.
+pub const SHIELD_STEPS: u32 = 8;
+
+/// Absorbs collision damage before it reaches the hull, and recharges while left alone.
+/// Strength is measured in the same units as `Sturdiness`.
+pub struct Shield {
+    pub strength: f32,
+    pub capacity: f32,
+    /// Seconds without being hit before the shield starts recharging.
+    pub recharge_delay: f32,
+    /// Strength regained per second while recharging.
+    pub recharge_rate: f32,
+    /// Hits at least this strong knock the shield down completely.
+    pub knockdown: f32,
+    /// Seconds since the shield was last hit.
+    pub since_hit: f32,
+    /// Radius of the bubble drawn around the ship.
+    pub radius: f32,
+}
+
+impl Shield {
+    pub fn new(config: &ShieldConfig, radius: f32) -> Self {
+        Self {
+            strength: config.capacity,
+            capacity: config.capacity,
+            recharge_delay: config.recharge_delay,
+            recharge_rate: config.recharge_rate,
+            knockdown: config.knockdown,
+            since_hit: 0.0,
+            radius,
+        }
+    }
+
+    /// Soak up as much of `damage` as possible. Returns the damage that gets through to the hull.
+    pub fn absorb(&mut self, damage: f32) -> f32 {
+        self.since_hit = 0.0;
+        let absorbed = damage.min(self.strength);
+        self.strength -= absorbed;
+        if damage >= self.knockdown {
+            self.strength = 0.0;
+        }
+        damage - absorbed
+    }
+
+    /// Current strength as one of `SHIELD_STEPS` levels, with 0 meaning down.
+    pub fn step(&self) -> u32 {
+        (self.strength / self.capacity * SHIELD_STEPS as f32).ceil() as u32
+    }
+}
+
+/// The bubble drawn around a shielded ship, and the level it was last drawn at.
+pub struct ShieldBubble {
+    pub step: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shield(capacity: f32, knockdown: f32) -> Shield {
+        let config = ShieldConfig {
+            capacity,
+            recharge_delay: 2.0,
+            recharge_rate: 5.0,
+            knockdown,
+        };
+        Shield::new(&config, 10.0)
+    }
+
+    #[test]
+    fn weak_hits_are_soaked_up() {
+        let mut shield = shield(10.0, 100.0);
+        shield.since_hit = 5.0;
+        assert_eq!(shield.absorb(4.0), 0.0);
+        assert_eq!(shield.strength, 6.0);
+        assert_eq!(shield.since_hit, 0.0);
+    }
+
+    #[test]
+    fn overflow_reaches_the_hull() {
+        let mut shield = shield(10.0, 100.0);
+        assert_eq!(shield.absorb(15.0), 5.0);
+        assert_eq!(shield.strength, 0.0);
+    }
+
+    #[test]
+    fn knockdown_drops_the_shield() {
+        let mut shield = shield(50.0, 20.0);
+        assert_eq!(shield.absorb(20.0), 0.0);
+        assert_eq!(shield.strength, 0.0);
+        assert_eq!(shield.step(), 0);
+    }
+
+    #[test]
+    fn steps_round_up() {
+        let mut shield = shield(8.0, 100.0);
+        assert_eq!(shield.step(), SHIELD_STEPS);
+        shield.absorb(7.5);
+        assert_eq!(shield.step(), 1);
+    }
+}
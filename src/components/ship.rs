@@ -1,6 +1,8 @@
+use serde::Deserialize;
+
 pub enum RotationDir {
-    LEFT,
-    RIGHT,
+    Left,
+    Right,
 }
 pub struct Controls {
     pub thrust: bool,
@@ -23,6 +25,57 @@ pub struct Engines {
     pub spin: f32,
 }
 
-pub struct Thruster;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum ThrusterKind {
+    /// Fires while thrusting.
+    Main,
+    /// Fires while turning left.
+    RotateLeft,
+    /// Fires while turning right.
+    RotateRight,
+}
+
+/// Where a thruster sits on a hull.
+/// The exhaust shape is drawn with its base at `position`, pointing down before `angle` is applied.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThrusterMount {
+    pub kind: ThrusterKind,
+    pub position: (f32, f32),
+    pub angle: f32,
+    pub scale: f32,
+}
+
+/// A ship's thruster flame. It stays attached to the ship and is scaled by its current `level`.
+pub struct Thruster {
+    pub mount: ThrusterMount,
+    /// How hard the thruster is firing, from 0 to 1. Ramps towards the ship's controls.
+    pub level: f32,
+}
 
 pub struct Fuel(pub f32);
+
+/// The weapon a ship fires from its hardpoints.
+pub struct Weapon {
+    pub name: String,
+    /// Shots left, or `None` for unlimited.
+    pub ammo: Option<u32>,
+    /// Extra bodies each bullet passes through.
+    pub piercing: u32,
+}
+
+impl Default for Weapon {
+    fn default() -> Self {
+        Self {
+            name: "Blaster".to_string(),
+            ammo: None,
+            piercing: 0,
+        }
+    }
+}
+
+/// Where a ship's bullets leave its hull, relative to its center.
+pub struct Hardpoints(pub Vec<(f32, f32)>);
+
+/// Where a ship's thrusters sit. Flames are attached to each mount when the ship spawns.
+pub struct ThrusterMounts(pub Vec<ThrusterMount>);
@@ -0,0 +1,22 @@
+use bevy::{prelude::Entity, utils::HashMap};
+
+use crate::config::{NebulaConfig, StarLayerConfig};
+
+/// What a background layer draws in each of its chunks.
+#[derive(Debug, Clone)]
+pub enum LayerContents {
+    Stars(StarLayerConfig),
+    Nebula(NebulaConfig),
+}
+
+/// A background layer that follows the camera by its parallax factor.
+/// Each chunk of the layer is a single child mesh, spawned when it comes into view
+/// and despawned when it leaves.
+pub struct BackgroundLayer {
+    /// Mixed into the seed, so layers don't repeat each other.
+    pub index: u64,
+    pub parallax: f32,
+    pub contents: LayerContents,
+    /// Chunks currently spawned, keyed by grid coordinates. `None` for chunks with nothing in them.
+    pub chunks: HashMap<(i32, i32), Option<Entity>>,
+}
@@ -0,0 +1,24 @@
+use bevy::prelude::{Entity, Vec2};
+
+/// A large structure the player can dock with to trade, repair and save.
+pub struct Station {
+    /// Center of the docking zone, in world space.
+    pub port: Vec2,
+}
+
+/// Root of the station menu shown while docked.
+pub struct StationMenu;
+
+pub struct StationMenuText;
+
+/// Where the player is docked, and where they come back after losing a ship.
+#[derive(Default)]
+pub struct Docking {
+    /// Station the ship is docked at. Kept after undocking until the ship leaves the docking zone,
+    /// so it doesn't dock again straight away.
+    pub station: Option<Entity>,
+    /// Docking port of the last station docked at during this run.
+    pub checkpoint: Option<Vec2>,
+    /// When the ship last docked, in seconds since startup.
+    pub docked_at: f64,
+}
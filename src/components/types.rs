@@ -1,14 +1,33 @@
 pub struct Bullet;
 pub struct Asteroid;
+/// A piece broken off a destroyed asteroid, which already paid out for being destroyed.
+pub struct Fragment;
 pub struct SpawnedAt(pub f64);
 pub struct DespawnAfter(pub f64);
 pub struct Sturdiness(pub f32);
+/// Sturdiness the entity spawned with.
+pub struct MaxSturdiness(pub f32);
+/// The outlines an entity is drawn from, in its local space. Most entities have just one.
+pub struct Outline(pub Vec<Vec<(f32, f32)>>);
 pub struct Player;
-
-#[derive(Default)]
-pub struct LastAsteroidSpawnTime(pub f64);
-
-pub struct ScoreText();
+pub struct Enemy;
 
 #[derive(Default, Debug)]
 pub struct Score(pub i32);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AppState {
+    /// Waiting for the game config to load.
+    Loading,
+    /// Picking a hull before a run.
+    HullSelect,
+    InGame,
+    /// Docked at a station, with the run paused underneath.
+    Docked,
+}
+
+/// Index of the chosen hull in the hull catalogue.
+#[derive(Default)]
+pub struct SelectedHull(pub usize);
+
+pub struct HullSelectText;
@@ -0,0 +1,1523 @@
+use std::{collections::HashMap, fmt};
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    math::Vec2,
+    prelude::Handle,
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use serde::Deserialize;
+
+use crate::components::{
+    biome::Biome,
+    boss::BossKind,
+    difficulty::{DifficultyPreset, DifficultyScaling},
+    economy::Ore,
+    material::Material,
+    mission::MissionKind,
+    pickup::PickupKind,
+    ship::ThrusterMount,
+};
+use crate::util::{hash_coords, nearest_site, polygon_area, unit_hash, value_noise};
+
+use crate::entities::{
+    asteroid::ASTEROID_SHAPE,
+    bullet::BULLET_SHAPE,
+    entity::check_outline,
+    ship::{EXHAUST_SHAPE, SPACESHIP_SHAPE, SPACESHIP_THRUSTERS},
+};
+
+pub const CONFIG_PATH: &str = "game.config.ron";
+pub const SHAPES_PATH: &str = "game.shapes.ron";
+pub const HULLS_PATH: &str = "game.hulls.ron";
+
+/// Tunables for the game, loaded from `assets/game.config.ron`.
+/// Every section and field is optional in the file and falls back to the defaults below.
+#[derive(Debug, Clone, Default, Deserialize, TypeUuid)]
+#[uuid = "5b6f1a0e-8f43-4c1e-9d4a-2f7f0c6b3e91"]
+#[serde(default, deny_unknown_fields)]
+pub struct GameConfig {
+    pub world: WorldConfig,
+    pub asteroid: AsteroidConfig,
+    pub weapon: WeaponConfig,
+    pub camera: CameraConfig,
+    pub controls: ControlsConfig,
+    pub enemy: EnemyConfig,
+    pub player: PlayerConfig,
+    pub hud: HudConfig,
+    pub starfield: StarfieldConfig,
+    pub biomes: BiomesConfig,
+    pub gravity: GravityConfig,
+    pub difficulty: DifficultyConfig,
+    pub loot: LootConfig,
+    pub economy: EconomyConfig,
+    pub stations: StationConfig,
+    pub missions: MissionConfig,
+    pub bosses: BossConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WorldConfig {
+    /// Side length of a square chunk of space, in pixels.
+    pub chunk_size: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShipConfig {
+    pub thrust: f32,
+    pub spin: f32,
+    /// Hull strength.
+    pub sturdiness: f32,
+    /// Fuel capacity.
+    pub fuel: f32,
+    /// Ore the cargo hold takes.
+    pub cargo: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EnemyConfig {
+    pub ship: ShipConfig,
+    /// Distance at which enemies start chasing the player.
+    pub aggro_range: f32,
+    /// Distance at which enemies open fire.
+    pub fire_range: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AsteroidConfig {
+    pub sturdiness: f32,
+    /// Scale applied to the asteroid outline.
+    pub size: f32,
+    /// Lines a destroyed asteroid is cut along, all through the point where it was last hit.
+    pub fracture_cuts: u32,
+    /// Pieces with less area than this crumble away instead of breaking off.
+    pub min_fragment_area: f32,
+    /// Share of the final blow's energy that goes into throwing the pieces apart.
+    pub fracture_energy: f32,
+    /// Fastest a piece flies away from where the asteroid was hit.
+    pub max_fracture_speed: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WeaponConfig {
+    pub bullet_speed: f32,
+    /// Seconds before a bullet that hit nothing despawns.
+    pub bullet_lifetime: f64,
+    /// Minimum number of seconds between shots.
+    pub cooldown: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CameraConfig {
+    /// Seconds of the player's velocity the camera looks ahead by.
+    pub look_ahead: f32,
+    /// Furthest the camera looks ahead, in pixels.
+    pub max_look_ahead: f32,
+    /// Roughly how long the camera takes to catch up with where it should be, in seconds.
+    pub smoothing: f32,
+    /// Zoom at rest. Larger values show more of the world.
+    pub min_zoom: f32,
+    /// Zoom at `zoom_speed` and above.
+    pub max_zoom: f32,
+    pub zoom_speed: f32,
+    /// Like `smoothing`, for zoom.
+    pub zoom_smoothing: f32,
+    /// Collision energy that shakes the camera as hard as it can.
+    pub shake_energy: f32,
+    /// Largest shake offset, in pixels.
+    pub max_shake: f32,
+    /// How quickly a full shake dies down, per second.
+    pub shake_decay: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ControlsConfig {
+    /// How far a finger has to drag before it counts as a touch control.
+    pub debounce_dist: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlayerConfig {
+    /// Ships the player can lose before the run ends.
+    pub lives: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HudConfig {
+    /// Distance from the ship to the edge of the minimap.
+    pub minimap_range: f32,
+    /// Off-screen objects due to hit the ship within this many seconds get an indicator.
+    pub threat_warning_time: f32,
+    /// Distance between threat indicators and the edge of the screen, in pixels.
+    pub threat_margin: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StarfieldConfig {
+    /// Star layers, each drawn as one mesh per chunk.
+    pub layers: Vec<StarLayerConfig>,
+    pub nebula: NebulaConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StarLayerConfig {
+    /// How much the layer follows the camera: 0 stays put like the asteroids, 1 is fixed to the screen.
+    pub parallax: f32,
+    pub stars_per_chunk: u32,
+    /// Side length of a star, in pixels.
+    pub size: f32,
+    /// From 0 for black to 1 for white.
+    pub brightness: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NebulaConfig {
+    pub parallax: f32,
+    /// Chance of a chunk holding a nebula cloud.
+    pub chance: f64,
+    pub min_radius: f32,
+    pub max_radius: f32,
+    pub opacity: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BiomesConfig {
+    /// Rough width of a biome region, in chunks.
+    pub region_size: f32,
+    pub biomes: Vec<BiomeConfig>,
+}
+
+/// How chunks in one kind of biome are populated.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BiomeConfig {
+    pub kind: Biome,
+    /// Share of space covered by this biome, relative to the others.
+    pub weight: f32,
+    pub asteroids_per_chunk: u32,
+    /// Smallest and largest asteroid, as multiples of `asteroid.size`.
+    pub size: (f32, f32),
+    /// Fastest an asteroid moves in a random direction.
+    pub max_speed: f32,
+    /// Speed every asteroid moves at in the region's shared drift direction.
+    pub drift: f32,
+    /// Asteroid materials with their relative odds.
+    pub materials: Vec<(Material, u32)>,
+    /// Chance of an enemy ship waiting in each chunk.
+    pub enemy_chance: f64,
+    /// Background color while the player is in the biome.
+    pub tint: (f32, f32, f32),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GravityConfig {
+    /// Chance of a chunk in a gravity well biome holding a well.
+    pub well_chance: f64,
+    /// Chance of a well being a black hole rather than a planetoid.
+    pub black_hole_chance: f64,
+    pub planetoid: WellConfig,
+    pub black_hole: WellConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WellConfig {
+    pub radius: f32,
+    /// Acceleration at a distance of one pixel. Falls off with the square of the distance.
+    pub strength: f32,
+    /// Distance beyond which the well has no pull, in pixels.
+    pub range: f32,
+}
+
+/// Difficulty rises with distance from the origin and time survived, one level at a time.
+/// Each curve is the fraction its quantity grows by per level.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DifficultyConfig {
+    /// Preset picked when the game starts.
+    pub preset: DifficultyPreset,
+    /// Distance from the origin that adds a level, in pixels.
+    pub distance_per_level: f32,
+    /// Seconds into a run that add a level.
+    pub time_per_level: f32,
+    pub max_level: f32,
+    pub asteroid_count: f32,
+    pub asteroid_speed: f32,
+    pub enemy_chance: f32,
+    pub loot_quality: f32,
+    /// Multiplier on every curve for each preset.
+    pub easy: f32,
+    pub normal: f32,
+    pub hard: f32,
+}
+
+impl DifficultyConfig {
+    /// Difficulty level at `distance` from the origin, `elapsed` seconds into a run.
+    pub fn level(&self, distance: f32, elapsed: f32) -> f32 {
+        (distance / self.distance_per_level + elapsed / self.time_per_level).min(self.max_level)
+    }
+
+    pub fn scaling(&self, preset: DifficultyPreset, level: f32) -> DifficultyScaling {
+        let multiplier = match preset {
+            DifficultyPreset::Easy => self.easy,
+            DifficultyPreset::Normal => self.normal,
+            DifficultyPreset::Hard => self.hard,
+        };
+        let curve = |growth: f32| multiplier * (1.0 + growth * level);
+        DifficultyScaling {
+            asteroid_count: curve(self.asteroid_count),
+            asteroid_speed: curve(self.asteroid_speed),
+            enemy_chance: curve(self.enemy_chance),
+            loot_quality: curve(self.loot_quality),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LootConfig {
+    pub asteroid: LootTable,
+    pub enemy: LootTable,
+    /// Seconds before an uncollected pickup disappears.
+    pub lifetime: f64,
+    /// Pickups closer to the player than this are pulled in.
+    pub magnet_radius: f32,
+    /// Speed pickups are pulled in at.
+    pub magnet_speed: f32,
+    /// Shots given by an ammo pickup of amount 1.
+    pub shots_per_ammo: f32,
+    /// Extra bodies each shot from ammo pickups passes through.
+    pub ammo_piercing: u32,
+}
+
+/// What something drops when it is destroyed.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LootTable {
+    /// Chance of dropping anything at all.
+    pub chance: f64,
+    pub drops: Vec<LootDrop>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LootDrop {
+    pub kind: PickupKind,
+    /// Odds of this drop relative to the others in the table.
+    pub weight: u32,
+    /// How much the pickup gives, before loot quality is applied.
+    pub amount: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EconomyConfig {
+    /// Ore an asteroid breaks into per unit of its mass.
+    pub ore_per_mass: f32,
+    /// Share of an asteroid's ore that comes out as each kind, by asteroid material.
+    pub compositions: HashMap<Material, Vec<(Ore, f32)>>,
+    /// Credits paid for each unit of ore.
+    pub ore_prices: HashMap<Ore, f32>,
+    /// Credits per unit of fuel.
+    pub fuel_price: f32,
+    /// Credits per point of sturdiness repaired.
+    pub repair_price: f32,
+    /// Price of the first level of an upgrade. Each level after that costs this much more.
+    pub upgrade_price: u32,
+    /// How much each upgrade level improves what it upgrades, as a fraction.
+    pub upgrade_step: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StationConfig {
+    /// Chance of a chunk holding a station.
+    pub chance: f64,
+    /// Radius of the docking zone above each station.
+    pub dock_radius: f32,
+    /// Fastest the ship can be moving relative to a station and still dock.
+    pub dock_max_speed: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MissionConfig {
+    /// Seconds between one mission ending and the next being offered.
+    pub interval: f32,
+    pub kinds: Vec<MissionRules>,
+    /// Asteroids to destroy in a destroy mission.
+    pub destroy_count: u32,
+    /// Nearest and furthest a waypoint is placed from where the mission starts.
+    pub waypoint_distance: (f32, f32),
+    /// How close the player or drone has to get to a waypoint to reach it.
+    pub arrival_radius: f32,
+    pub escort_speed: f32,
+    /// The drone waits whenever the player is further away than this.
+    pub escort_range: f32,
+    pub escort_sturdiness: f32,
+    /// Seconds a station has to hold out.
+    pub defend_time: f32,
+    /// Furthest away a station can be to be offered for defence.
+    pub defend_range: f32,
+    pub defend_integrity: f32,
+    /// Integrity each raider within firing range of the station takes away per second.
+    pub raider_damage: f32,
+    /// Raiders in each wave.
+    pub raiders: u32,
+    /// Seconds between raider waves.
+    pub raid_interval: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BossConfig {
+    /// Distance from the origin between boss encounters.
+    pub milestone_distance: f32,
+    /// Bosses met at each milestone in turn, starting over after the last.
+    pub order: Vec<BossKind>,
+    /// How far ahead of the player a boss appears.
+    pub spawn_distance: f32,
+    /// The boss health bar shows while a boss is this close.
+    pub engage_range: f32,
+    /// Sturdiness of each armored part.
+    pub armor: f32,
+    /// Sturdiness of each weak point.
+    pub weak_point: f32,
+    /// Segments between the worm's head and tail.
+    pub worm_segments: u32,
+    pub worm_speed: f32,
+    pub carrier_speed: f32,
+    /// Distance the carrier tries to keep from the player.
+    pub carrier_standoff: f32,
+    /// Seconds between fighter launches in the first phase. Later phases launch faster.
+    pub launch_interval: f32,
+    /// Most fighters a carrier can have out at once.
+    pub max_fighters: u32,
+    pub golem_speed: f32,
+    /// How fast the golem spins in the first phase, in radians per second.
+    pub golem_spin: f32,
+}
+
+/// How often a kind of mission comes up and what it pays.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MissionRules {
+    pub kind: MissionKind,
+    /// Odds of this kind relative to the others.
+    pub weight: u32,
+    pub credits: u32,
+    pub score: i32,
+}
+
+impl BiomesConfig {
+    /// Biome at the given chunk grid coordinates.
+    pub fn sample(&self, seed: u64, x: i32, y: i32) -> Option<&BiomeConfig> {
+        const BIOME_SALT: u64 = 0xB10E_5EED_0000_0001;
+        let p = Vec2::new(x as f32, y as f32) / self.region_size;
+        // Regions all cover the same area on average and each rolls for its biome on its own,
+        // so weights hold as shares of space.
+        let (rx, ry) = nearest_site(seed, p);
+        let total: f32 = self.biomes.iter().map(|biome| biome.weight).sum();
+        let mut remaining = unit_hash(hash_coords(seed ^ BIOME_SALT, rx, ry)) * total;
+        self.biomes
+            .iter()
+            .find(|biome| {
+                remaining -= biome.weight;
+                remaining < 0.0
+            })
+            .or_else(|| self.biomes.last())
+    }
+
+    /// Direction things drift in at the given chunk grid coordinates, as a unit vector.
+    /// Changes slowly enough that a whole region shares roughly the same direction.
+    pub fn drift_direction(&self, seed: u64, x: i32, y: i32) -> Vec2 {
+        const DRIFT_SALT: u64 = 0xD81F_7A11_5EED_0001;
+        let p = Vec2::new(x as f32, y as f32) / self.region_size;
+        // Noise bunches up around the middle, so go round twice to spread directions out.
+        let angle = value_noise(seed ^ DRIFT_SALT, p) * 2.0 * std::f32::consts::TAU;
+        Vec2::new(angle.cos(), angle.sin())
+    }
+}
+
+impl Default for WorldConfig {
+    fn default() -> Self {
+        Self { chunk_size: 600.0 }
+    }
+}
+
+impl Default for ShipConfig {
+    fn default() -> Self {
+        Self {
+            thrust: 50000.0,
+            spin: 5.0,
+            sturdiness: 100.0,
+            fuel: 100.0,
+            cargo: 50.0,
+        }
+    }
+}
+
+impl Default for EnemyConfig {
+    fn default() -> Self {
+        Self {
+            ship: ShipConfig {
+                thrust: 30000.0,
+                spin: 3.0,
+                sturdiness: 40.0,
+                fuel: 100.0,
+                cargo: 0.0,
+            },
+            aggro_range: 800.0,
+            fire_range: 400.0,
+        }
+    }
+}
+
+impl Default for AsteroidConfig {
+    fn default() -> Self {
+        Self {
+            sturdiness: 25.0,
+            size: 4.0,
+            fracture_cuts: 2,
+            min_fragment_area: 80.0,
+            fracture_energy: 0.2,
+            max_fracture_speed: 120.0,
+        }
+    }
+}
+
+impl Default for WeaponConfig {
+    fn default() -> Self {
+        Self {
+            bullet_speed: 500.0,
+            bullet_lifetime: 3.0,
+            cooldown: 0.25,
+        }
+    }
+}
+
+impl Default for CameraConfig {
+    fn default() -> Self {
+        Self {
+            look_ahead: 0.5,
+            max_look_ahead: 250.0,
+            smoothing: 0.3,
+            min_zoom: 1.0,
+            max_zoom: 1.8,
+            zoom_speed: 600.0,
+            zoom_smoothing: 0.8,
+            shake_energy: 2000000.0,
+            max_shake: 20.0,
+            shake_decay: 1.5,
+        }
+    }
+}
+
+impl Default for ControlsConfig {
+    fn default() -> Self {
+        Self {
+            debounce_dist: 10.0,
+        }
+    }
+}
+
+impl Default for StarfieldConfig {
+    fn default() -> Self {
+        let layer = |parallax, stars_per_chunk, size, brightness| StarLayerConfig {
+            parallax,
+            stars_per_chunk,
+            size,
+            brightness,
+        };
+        Self {
+            layers: vec![
+                layer(0.9, 60, 1.0, 0.35),
+                layer(0.7, 30, 1.5, 0.55),
+                layer(0.4, 12, 2.0, 0.8),
+            ],
+            nebula: Default::default(),
+        }
+    }
+}
+
+impl Default for NebulaConfig {
+    fn default() -> Self {
+        Self {
+            parallax: 0.95,
+            chance: 0.3,
+            min_radius: 150.0,
+            max_radius: 400.0,
+            opacity: 0.06,
+        }
+    }
+}
+
+impl Default for BiomesConfig {
+    fn default() -> Self {
+        use Material::*;
+        let biome = |kind, weight, asteroids_per_chunk, size, max_speed, drift| BiomeConfig {
+            kind,
+            weight,
+            asteroids_per_chunk,
+            size,
+            max_speed,
+            drift,
+            materials: vec![(Rock, 6), (Ice, 2), (Metal, 1), (Crystal, 1)],
+            enemy_chance: 0.0,
+            tint: (0.0, 0.0, 0.0),
+        };
+        Self {
+            region_size: 6.0,
+            biomes: vec![
+                BiomeConfig {
+                    tint: (0.0, 0.0, 0.01),
+                    ..biome(Biome::Void, 1.0, 0, (1.0, 1.0), 0.0, 0.0)
+                },
+                biome(Biome::Sparse, 2.0, 2, (1.0, 1.0), 100.0, 0.0),
+                BiomeConfig {
+                    materials: vec![(Rock, 6), (Metal, 1)],
+                    tint: (0.03, 0.02, 0.01),
+                    ..biome(Biome::Field, 2.0, 6, (0.7, 1.8), 40.0, 0.0)
+                },
+                BiomeConfig {
+                    materials: vec![(Ice, 8), (Crystal, 1)],
+                    tint: (0.0, 0.03, 0.06),
+                    ..biome(Biome::IceBelt, 1.0, 4, (0.8, 1.4), 60.0, 15.0)
+                },
+                BiomeConfig {
+                    materials: vec![(Rock, 3), (Metal, 2)],
+                    tint: (0.03, 0.01, 0.0),
+                    ..biome(Biome::DebrisStream, 1.0, 5, (0.4, 0.8), 30.0, 250.0)
+                },
+                BiomeConfig {
+                    tint: (0.03, 0.0, 0.05),
+                    ..biome(Biome::GravityWell, 0.5, 3, (0.8, 1.2), 60.0, 0.0)
+                },
+                BiomeConfig {
+                    enemy_chance: 0.4,
+                    tint: (0.05, 0.0, 0.0),
+                    ..biome(Biome::EnemyTerritory, 1.0, 1, (1.0, 1.0), 80.0, 0.0)
+                },
+            ],
+        }
+    }
+}
+
+impl Default for GravityConfig {
+    fn default() -> Self {
+        Self {
+            well_chance: 0.3,
+            black_hole_chance: 0.2,
+            planetoid: WellConfig {
+                radius: 80.0,
+                strength: 2000000.0,
+                range: 900.0,
+            },
+            black_hole: WellConfig {
+                radius: 15.0,
+                strength: 4000000.0,
+                range: 1200.0,
+            },
+        }
+    }
+}
+
+impl Default for DifficultyConfig {
+    fn default() -> Self {
+        Self {
+            preset: DifficultyPreset::Normal,
+            distance_per_level: 10000.0,
+            time_per_level: 300.0,
+            max_level: 5.0,
+            asteroid_count: 0.3,
+            asteroid_speed: 0.15,
+            enemy_chance: 0.5,
+            loot_quality: 0.2,
+            easy: 0.7,
+            normal: 1.0,
+            hard: 1.4,
+        }
+    }
+}
+
+impl Default for LootConfig {
+    fn default() -> Self {
+        use crate::components::pickup::PowerUp;
+        let drop = |kind, weight, amount| LootDrop {
+            kind,
+            weight,
+            amount,
+        };
+        Self {
+            asteroid: LootTable {
+                chance: 0.2,
+                drops: vec![
+                    drop(PickupKind::Fuel, 4, 20.0),
+                    drop(PickupKind::Repair, 2, 15.0),
+                    drop(PickupKind::Credits, 4, 5.0),
+                    drop(PickupKind::Ammo, 1, 1.0),
+                ],
+            },
+            enemy: LootTable {
+                chance: 0.8,
+                drops: vec![
+                    drop(PickupKind::Ammo, 3, 1.0),
+                    drop(PickupKind::Credits, 3, 20.0),
+                    drop(PickupKind::Repair, 2, 30.0),
+                    drop(PickupKind::PowerUp(PowerUp::DoubleScore), 1, 15.0),
+                    drop(PickupKind::PowerUp(PowerUp::Overdrive), 1, 10.0),
+                ],
+            },
+            lifetime: 20.0,
+            magnet_radius: 150.0,
+            magnet_speed: 300.0,
+            shots_per_ammo: 20.0,
+            ammo_piercing: 2,
+        }
+    }
+}
+
+impl Default for EconomyConfig {
+    fn default() -> Self {
+        Self {
+            ore_per_mass: 0.02,
+            compositions: vec![
+                (Material::Rock, vec![(Ore::Iron, 0.3)]),
+                (Material::Ice, vec![(Ore::Ice, 1.0)]),
+                (Material::Metal, vec![(Ore::Iron, 1.0)]),
+                (
+                    Material::Crystal,
+                    vec![(Ore::Crystal, 0.7), (Ore::Ice, 0.3)],
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            ore_prices: vec![(Ore::Iron, 2.0), (Ore::Ice, 1.0), (Ore::Crystal, 6.0)]
+                .into_iter()
+                .collect(),
+            fuel_price: 0.5,
+            repair_price: 1.0,
+            upgrade_price: 100,
+            upgrade_step: 0.15,
+        }
+    }
+}
+
+impl Default for MissionConfig {
+    fn default() -> Self {
+        let rules = |kind, weight, credits, score| MissionRules {
+            kind,
+            weight,
+            credits,
+            score,
+        };
+        Self {
+            interval: 15.0,
+            kinds: vec![
+                rules(MissionKind::Destroy, 3, 100, 10),
+                rules(MissionKind::Escort, 2, 250, 25),
+                rules(MissionKind::Reach, 3, 150, 15),
+                rules(MissionKind::Defend, 2, 300, 30),
+                rules(MissionKind::Boss, 1, 500, 50),
+            ],
+            destroy_count: 10,
+            waypoint_distance: (1500.0, 3000.0),
+            arrival_radius: 60.0,
+            escort_speed: 60.0,
+            escort_range: 400.0,
+            escort_sturdiness: 300.0,
+            defend_time: 60.0,
+            defend_range: 1800.0,
+            defend_integrity: 100.0,
+            raider_damage: 2.0,
+            raiders: 2,
+            raid_interval: 20.0,
+        }
+    }
+}
+
+impl Default for BossConfig {
+    fn default() -> Self {
+        Self {
+            milestone_distance: 8000.0,
+            order: vec![BossKind::Worm, BossKind::Carrier, BossKind::Golem],
+            spawn_distance: 900.0,
+            engage_range: 1200.0,
+            armor: 2000.0,
+            weak_point: 600.0,
+            worm_segments: 8,
+            worm_speed: 90.0,
+            carrier_speed: 50.0,
+            carrier_standoff: 500.0,
+            launch_interval: 12.0,
+            max_fighters: 4,
+            golem_speed: 40.0,
+            golem_spin: 0.8,
+        }
+    }
+}
+
+impl Default for StationConfig {
+    fn default() -> Self {
+        Self {
+            chance: 0.03,
+            dock_radius: 20.0,
+            dock_max_speed: 30.0,
+        }
+    }
+}
+
+impl Default for PlayerConfig {
+    fn default() -> Self {
+        Self { lives: 3 }
+    }
+}
+
+impl Default for HudConfig {
+    fn default() -> Self {
+        Self {
+            minimap_range: 1500.0,
+            threat_warning_time: 6.0,
+            threat_margin: 24.0,
+        }
+    }
+}
+
+/// Outlines of the game's entities, loaded from `assets/game.shapes.ron`.
+/// Each outline is a list of points relative to the entity's center.
+#[derive(Debug, Clone, Deserialize, TypeUuid)]
+#[uuid = "0d1c8e4a-3b6f-4f0e-a6d2-7c9e5b1f2a48"]
+#[serde(default, deny_unknown_fields)]
+pub struct Shapes {
+    pub ship: Vec<(f32, f32)>,
+    /// Drawn at each thruster mount, with its base at the mount point and pointing down.
+    pub exhaust: Vec<(f32, f32)>,
+    /// Where the ship's thrusters sit.
+    pub ship_thrusters: Vec<ThrusterMount>,
+    /// Unscaled; multiplied by `asteroid.size` from the game config.
+    pub asteroid: Vec<(f32, f32)>,
+    pub bullet: Vec<(f32, f32)>,
+}
+
+impl Default for Shapes {
+    fn default() -> Self {
+        Self {
+            ship: SPACESHIP_SHAPE.clone(),
+            exhaust: EXHAUST_SHAPE.clone(),
+            ship_thrusters: SPACESHIP_THRUSTERS.clone(),
+            asteroid: ASTEROID_SHAPE.clone(),
+            bullet: BULLET_SHAPE.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ShieldConfig {
+    /// Damage absorbed before the hull is hit, in the same units as `sturdiness`.
+    pub capacity: f32,
+    /// Seconds without being hit before the shield recharges.
+    pub recharge_delay: f32,
+    /// Strength regained per second while recharging.
+    pub recharge_rate: f32,
+    /// Hits at least this strong knock the shield down completely.
+    pub knockdown: f32,
+}
+
+/// A ship the player can fly, from the hull catalogue.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Hull {
+    pub name: String,
+    /// Outline of the hull, also used as its collider.
+    pub shape: Vec<(f32, f32)>,
+    pub mass: f32,
+    pub ship: ShipConfig,
+    /// Where bullets leave the hull, relative to its center with the nose pointing up.
+    pub hardpoints: Vec<(f32, f32)>,
+    pub thrusters: Vec<ThrusterMount>,
+    #[serde(default)]
+    pub shield: Option<ShieldConfig>,
+    /// Best score needed before the hull can be picked.
+    #[serde(default)]
+    pub unlock_score: i32,
+}
+
+impl Hull {
+    /// A hull built from the shared ship outline, as flown by enemies.
+    pub fn from_shapes(name: &str, ship: &ShipConfig, shapes: &Shapes) -> Self {
+        Self {
+            name: name.to_string(),
+            shape: shapes.ship.clone(),
+            mass: polygon_area(&shapes.ship) * Material::Metal.props().density,
+            ship: ship.clone(),
+            hardpoints: vec![(0.0, 20.0)],
+            thrusters: shapes.ship_thrusters.clone(),
+            shield: None,
+            unlock_score: 0,
+        }
+    }
+}
+
+/// Hulls to choose from before a run, loaded from `assets/game.hulls.ron`.
+#[derive(Debug, Clone, Deserialize, TypeUuid)]
+#[uuid = "a3e7c2d9-61b4-4f8a-9e05-3c8d7b2f1e64"]
+#[serde(deny_unknown_fields)]
+pub struct HullCatalogue {
+    pub hulls: Vec<Hull>,
+}
+
+impl Default for HullCatalogue {
+    fn default() -> Self {
+        Self {
+            hulls: vec![Hull::from_shapes(
+                "Arrow",
+                &ShipConfig::default(),
+                &Shapes::default(),
+            )],
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The file isn't valid RON, or a field has the wrong type or an unknown name.
+    Parse(ron::Error),
+    /// The file parsed, but a field holds a value the game can't use.
+    Invalid {
+        field: &'static str,
+        reason: &'static str,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Parse(err) => write!(f, "could not parse config: {}", err),
+            ConfigError::Invalid { field, reason } => {
+                write!(f, "invalid value for `{}`: {}", field, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+fn check(ok: bool, field: &'static str, reason: &'static str) -> Result<(), ConfigError> {
+    if ok {
+        Ok(())
+    } else {
+        Err(ConfigError::Invalid { field, reason })
+    }
+}
+
+/// Check that an outline can be made into a body.
+fn check_shape(points: &[(f32, f32)], field: &'static str) -> Result<(), ConfigError> {
+    check_outline(points).map_err(|err| ConfigError::Invalid {
+        field,
+        reason: err.reason(),
+    })
+}
+
+impl GameConfig {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ConfigError> {
+        let config: GameConfig = ron::de::from_bytes(bytes).map_err(ConfigError::Parse)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        const POSITIVE: &str = "must be greater than zero";
+        const NON_NEGATIVE: &str = "must not be negative";
+
+        check(self.world.chunk_size > 0.0, "world.chunk_size", POSITIVE)?;
+        check(
+            self.asteroid.sturdiness > 0.0,
+            "asteroid.sturdiness",
+            POSITIVE,
+        )?;
+        check(self.asteroid.size > 0.0, "asteroid.size", POSITIVE)?;
+        check(
+            self.asteroid.min_fragment_area > 0.0,
+            "asteroid.min_fragment_area",
+            POSITIVE,
+        )?;
+        check(
+            (0.0..=1.0).contains(&self.asteroid.fracture_energy),
+            "asteroid.fracture_energy",
+            "must be between 0 and 1",
+        )?;
+        check(
+            self.asteroid.max_fracture_speed >= 0.0,
+            "asteroid.max_fracture_speed",
+            NON_NEGATIVE,
+        )?;
+        check(
+            self.weapon.bullet_speed > 0.0,
+            "weapon.bullet_speed",
+            POSITIVE,
+        )?;
+        check(
+            self.weapon.bullet_lifetime > 0.0,
+            "weapon.bullet_lifetime",
+            POSITIVE,
+        )?;
+        check(self.weapon.cooldown >= 0.0, "weapon.cooldown", NON_NEGATIVE)?;
+        check(
+            self.camera.look_ahead >= 0.0,
+            "camera.look_ahead",
+            NON_NEGATIVE,
+        )?;
+        check(
+            self.camera.max_look_ahead >= 0.0,
+            "camera.max_look_ahead",
+            NON_NEGATIVE,
+        )?;
+        check(self.camera.smoothing > 0.0, "camera.smoothing", POSITIVE)?;
+        check(self.camera.min_zoom > 0.0, "camera.min_zoom", POSITIVE)?;
+        check(
+            self.camera.max_zoom >= self.camera.min_zoom,
+            "camera.max_zoom",
+            "must not be less than camera.min_zoom",
+        )?;
+        check(self.camera.zoom_speed > 0.0, "camera.zoom_speed", POSITIVE)?;
+        check(
+            self.camera.zoom_smoothing > 0.0,
+            "camera.zoom_smoothing",
+            POSITIVE,
+        )?;
+        check(
+            self.camera.shake_energy > 0.0,
+            "camera.shake_energy",
+            POSITIVE,
+        )?;
+        check(
+            self.camera.max_shake >= 0.0,
+            "camera.max_shake",
+            NON_NEGATIVE,
+        )?;
+        check(
+            self.camera.shake_decay >= 0.0,
+            "camera.shake_decay",
+            NON_NEGATIVE,
+        )?;
+        check(
+            self.controls.debounce_dist >= 0.0,
+            "controls.debounce_dist",
+            NON_NEGATIVE,
+        )?;
+        check(
+            self.enemy.ship.thrust >= 0.0,
+            "enemy.ship.thrust",
+            NON_NEGATIVE,
+        )?;
+        check(self.enemy.ship.spin >= 0.0, "enemy.ship.spin", NON_NEGATIVE)?;
+        check(
+            self.enemy.ship.sturdiness > 0.0,
+            "enemy.ship.sturdiness",
+            POSITIVE,
+        )?;
+        check(
+            self.enemy.aggro_range >= 0.0,
+            "enemy.aggro_range",
+            NON_NEGATIVE,
+        )?;
+        check(
+            self.enemy.fire_range >= 0.0,
+            "enemy.fire_range",
+            NON_NEGATIVE,
+        )?;
+        check(self.player.lives > 0, "player.lives", POSITIVE)?;
+        for layer in self.starfield.layers.iter() {
+            check(
+                (0.0..1.0).contains(&layer.parallax),
+                "starfield.layers.parallax",
+                "must be at least 0 and less than 1",
+            )?;
+            check(layer.size > 0.0, "starfield.layers.size", POSITIVE)?;
+        }
+        let nebula = &self.starfield.nebula;
+        check(
+            (0.0..1.0).contains(&nebula.parallax),
+            "starfield.nebula.parallax",
+            "must be at least 0 and less than 1",
+        )?;
+        check(
+            (0.0..=1.0).contains(&nebula.chance),
+            "starfield.nebula.chance",
+            "must be between 0 and 1",
+        )?;
+        check(
+            nebula.min_radius > 0.0 && nebula.max_radius >= nebula.min_radius,
+            "starfield.nebula.max_radius",
+            "must not be less than min_radius, which must be greater than zero",
+        )?;
+        check(
+            self.biomes.region_size > 0.0,
+            "biomes.region_size",
+            POSITIVE,
+        )?;
+        check(
+            self.biomes.biomes.iter().any(|biome| biome.weight > 0.0),
+            "biomes.biomes",
+            "needs at least one biome with a weight greater than zero",
+        )?;
+        for biome in self.biomes.biomes.iter() {
+            check(biome.weight >= 0.0, "biomes.biomes.weight", NON_NEGATIVE)?;
+            check(
+                biome.size.0 > 0.0 && biome.size.1 >= biome.size.0,
+                "biomes.biomes.size",
+                "must be a range of sizes greater than zero, smallest first",
+            )?;
+            check(
+                biome.max_speed >= 0.0,
+                "biomes.biomes.max_speed",
+                NON_NEGATIVE,
+            )?;
+            check(biome.drift >= 0.0, "biomes.biomes.drift", NON_NEGATIVE)?;
+            check(
+                biome.materials.iter().any(|(_, weight)| *weight > 0),
+                "biomes.biomes.materials",
+                "needs at least one material with odds greater than zero",
+            )?;
+            check(
+                (0.0..=1.0).contains(&biome.enemy_chance),
+                "biomes.biomes.enemy_chance",
+                "must be between 0 and 1",
+            )?;
+        }
+        check(
+            (0.0..=1.0).contains(&self.gravity.well_chance),
+            "gravity.well_chance",
+            "must be between 0 and 1",
+        )?;
+        check(
+            (0.0..=1.0).contains(&self.gravity.black_hole_chance),
+            "gravity.black_hole_chance",
+            "must be between 0 and 1",
+        )?;
+        for (field, well) in [
+            ("gravity.planetoid", &self.gravity.planetoid),
+            ("gravity.black_hole", &self.gravity.black_hole),
+        ] {
+            check(
+                well.radius > 0.0 && well.strength >= 0.0 && well.range > well.radius,
+                field,
+                "needs a radius greater than zero, a strength that isn't negative and a range beyond the radius",
+            )?;
+        }
+        let difficulty = &self.difficulty;
+        check(
+            difficulty.distance_per_level > 0.0,
+            "difficulty.distance_per_level",
+            POSITIVE,
+        )?;
+        check(
+            difficulty.time_per_level > 0.0,
+            "difficulty.time_per_level",
+            POSITIVE,
+        )?;
+        check(
+            difficulty.max_level >= 0.0,
+            "difficulty.max_level",
+            NON_NEGATIVE,
+        )?;
+        for (field, value) in [
+            ("difficulty.asteroid_count", difficulty.asteroid_count),
+            ("difficulty.asteroid_speed", difficulty.asteroid_speed),
+            ("difficulty.enemy_chance", difficulty.enemy_chance),
+            ("difficulty.loot_quality", difficulty.loot_quality),
+            ("difficulty.easy", difficulty.easy),
+            ("difficulty.normal", difficulty.normal),
+            ("difficulty.hard", difficulty.hard),
+        ] {
+            check(value >= 0.0, field, NON_NEGATIVE)?;
+        }
+        for (field, table) in [
+            ("loot.asteroid", &self.loot.asteroid),
+            ("loot.enemy", &self.loot.enemy),
+        ] {
+            check(
+                (0.0..=1.0).contains(&table.chance)
+                    && table.drops.iter().all(|drop| drop.amount > 0.0),
+                field,
+                "needs a chance between 0 and 1 and drops with amounts greater than zero",
+            )?;
+        }
+        check(self.loot.lifetime > 0.0, "loot.lifetime", POSITIVE)?;
+        check(
+            self.loot.magnet_radius >= 0.0,
+            "loot.magnet_radius",
+            NON_NEGATIVE,
+        )?;
+        check(
+            self.loot.magnet_speed >= 0.0,
+            "loot.magnet_speed",
+            NON_NEGATIVE,
+        )?;
+        check(
+            self.loot.shots_per_ammo > 0.0,
+            "loot.shots_per_ammo",
+            POSITIVE,
+        )?;
+        let economy = &self.economy;
+        check(
+            economy.ore_per_mass >= 0.0,
+            "economy.ore_per_mass",
+            NON_NEGATIVE,
+        )?;
+        check(
+            economy
+                .compositions
+                .values()
+                .flatten()
+                .all(|(_, share)| *share >= 0.0),
+            "economy.compositions",
+            "shares must not be negative",
+        )?;
+        check(
+            economy.ore_prices.values().all(|price| *price >= 0.0),
+            "economy.ore_prices",
+            "prices must not be negative",
+        )?;
+        check(
+            economy.fuel_price >= 0.0,
+            "economy.fuel_price",
+            NON_NEGATIVE,
+        )?;
+        check(
+            economy.repair_price >= 0.0,
+            "economy.repair_price",
+            NON_NEGATIVE,
+        )?;
+        check(
+            economy.upgrade_step >= 0.0,
+            "economy.upgrade_step",
+            NON_NEGATIVE,
+        )?;
+        check(
+            (0.0..=1.0).contains(&self.stations.chance),
+            "stations.chance",
+            "must be between 0 and 1",
+        )?;
+        check(
+            self.stations.dock_radius > 0.0,
+            "stations.dock_radius",
+            POSITIVE,
+        )?;
+        check(
+            self.stations.dock_max_speed >= 0.0,
+            "stations.dock_max_speed",
+            NON_NEGATIVE,
+        )?;
+        let missions = &self.missions;
+        check(missions.interval >= 0.0, "missions.interval", NON_NEGATIVE)?;
+        check(
+            missions.kinds.iter().any(|rules| rules.weight > 0),
+            "missions.kinds",
+            "needs at least one kind with odds greater than zero",
+        )?;
+        check(
+            missions.waypoint_distance.0 > 0.0
+                && missions.waypoint_distance.0 <= missions.waypoint_distance.1,
+            "missions.waypoint_distance",
+            "needs a nearest distance greater than zero and no further than the furthest",
+        )?;
+        for (field, value) in [
+            ("missions.arrival_radius", missions.arrival_radius),
+            ("missions.escort_speed", missions.escort_speed),
+            ("missions.escort_range", missions.escort_range),
+            ("missions.escort_sturdiness", missions.escort_sturdiness),
+            ("missions.defend_time", missions.defend_time),
+            ("missions.defend_integrity", missions.defend_integrity),
+            ("missions.raid_interval", missions.raid_interval),
+        ] {
+            check(value > 0.0, field, POSITIVE)?;
+        }
+        check(
+            missions.defend_range >= 0.0,
+            "missions.defend_range",
+            NON_NEGATIVE,
+        )?;
+        check(
+            missions.raider_damage >= 0.0,
+            "missions.raider_damage",
+            NON_NEGATIVE,
+        )?;
+        let bosses = &self.bosses;
+        check(
+            !bosses.order.is_empty(),
+            "bosses.order",
+            "needs at least one boss",
+        )?;
+        for (field, value) in [
+            ("bosses.milestone_distance", bosses.milestone_distance),
+            ("bosses.spawn_distance", bosses.spawn_distance),
+            ("bosses.armor", bosses.armor),
+            ("bosses.weak_point", bosses.weak_point),
+            ("bosses.launch_interval", bosses.launch_interval),
+        ] {
+            check(value > 0.0, field, POSITIVE)?;
+        }
+        for (field, value) in [
+            ("bosses.engage_range", bosses.engage_range),
+            ("bosses.worm_speed", bosses.worm_speed),
+            ("bosses.carrier_speed", bosses.carrier_speed),
+            ("bosses.carrier_standoff", bosses.carrier_standoff),
+            ("bosses.golem_speed", bosses.golem_speed),
+            ("bosses.golem_spin", bosses.golem_spin),
+        ] {
+            check(value >= 0.0, field, NON_NEGATIVE)?;
+        }
+        check(self.hud.minimap_range > 0.0, "hud.minimap_range", POSITIVE)?;
+        check(
+            self.hud.threat_warning_time > 0.0,
+            "hud.threat_warning_time",
+            POSITIVE,
+        )?;
+        check(
+            self.hud.threat_margin >= 0.0,
+            "hud.threat_margin",
+            NON_NEGATIVE,
+        )?;
+        Ok(())
+    }
+}
+
+impl Shapes {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ConfigError> {
+        let shapes: Shapes = ron::de::from_bytes(bytes).map_err(ConfigError::Parse)?;
+        shapes.validate()?;
+        Ok(shapes)
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        const TOO_FEW_POINTS: &str = "an outline needs at least 3 points";
+
+        check_shape(&self.ship, "ship")?;
+        check(self.exhaust.len() >= 3, "exhaust", TOO_FEW_POINTS)?;
+        check(
+            self.ship_thrusters.iter().all(|mount| mount.scale > 0.0),
+            "ship_thrusters.scale",
+            "must be greater than zero",
+        )?;
+        check_shape(&self.asteroid, "asteroid")?;
+        check_shape(&self.bullet, "bullet")?;
+        Ok(())
+    }
+}
+
+impl HullCatalogue {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ConfigError> {
+        let catalogue: HullCatalogue = ron::de::from_bytes(bytes).map_err(ConfigError::Parse)?;
+        catalogue.validate()?;
+        Ok(catalogue)
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        const POSITIVE: &str = "must be greater than zero";
+        const NON_NEGATIVE: &str = "must not be negative";
+
+        check(
+            self.hulls.iter().any(|hull| hull.unlock_score <= 0),
+            "hulls.unlock_score",
+            "at least one hull must be unlocked from the start",
+        )?;
+        for hull in self.hulls.iter() {
+            check_shape(&hull.shape, "hulls.shape")?;
+            check(hull.mass > 0.0, "hulls.mass", POSITIVE)?;
+            check(hull.ship.thrust >= 0.0, "hulls.ship.thrust", NON_NEGATIVE)?;
+            check(hull.ship.spin >= 0.0, "hulls.ship.spin", NON_NEGATIVE)?;
+            check(
+                hull.ship.sturdiness > 0.0,
+                "hulls.ship.sturdiness",
+                POSITIVE,
+            )?;
+            check(hull.ship.fuel >= 0.0, "hulls.ship.fuel", NON_NEGATIVE)?;
+            check(
+                !hull.hardpoints.is_empty(),
+                "hulls.hardpoints",
+                "a hull needs at least one hardpoint",
+            )?;
+            check(
+                hull.thrusters.iter().all(|mount| mount.scale > 0.0),
+                "hulls.thrusters.scale",
+                POSITIVE,
+            )?;
+            if let Some(shield) = &hull.shield {
+                check(shield.capacity > 0.0, "hulls.shield.capacity", POSITIVE)?;
+                check(
+                    shield.recharge_delay >= 0.0,
+                    "hulls.shield.recharge_delay",
+                    NON_NEGATIVE,
+                )?;
+                check(
+                    shield.recharge_rate >= 0.0,
+                    "hulls.shield.recharge_rate",
+                    NON_NEGATIVE,
+                )?;
+                check(shield.knockdown > 0.0, "hulls.shield.knockdown", POSITIVE)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Handle to the config asset, kept alive so it isn't unloaded.
+pub struct ConfigHandle(pub Handle<GameConfig>);
+
+/// Handle to the shapes asset, kept alive so it isn't unloaded.
+pub struct ShapesHandle(pub Handle<Shapes>);
+
+/// Handle to the hull catalogue asset, kept alive so it isn't unloaded.
+pub struct HullsHandle(pub Handle<HullCatalogue>);
+
+#[derive(Default)]
+pub struct ConfigLoader;
+
+impl AssetLoader for ConfigLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let config = GameConfig::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(config));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["config.ron"]
+    }
+}
+
+#[derive(Default)]
+pub struct ShapesLoader;
+
+impl AssetLoader for ShapesLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let shapes = Shapes::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(shapes));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["shapes.ron"]
+    }
+}
+
+#[derive(Default)]
+pub struct HullsLoader;
+
+impl AssetLoader for HullsLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let catalogue = HullCatalogue::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(catalogue));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["hulls.ron"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn field_of(result: Result<GameConfig, ConfigError>) -> &'static str {
+        match result {
+            Err(ConfigError::Invalid { field, .. }) => field,
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn defaults_are_valid() {
+        GameConfig::default().validate().unwrap();
+    }
+
+    #[test]
+    fn shipped_config_loads() {
+        GameConfig::from_bytes(include_bytes!("../assets/game.config.ron")).unwrap();
+    }
+
+    #[test]
+    fn missing_sections_fall_back_to_defaults() {
+        let config = GameConfig::from_bytes(b"(world: (chunk_size: 500.0))").unwrap();
+        assert_eq!(config.world.chunk_size, 500.0);
+        assert_eq!(config.camera.min_zoom, CameraConfig::default().min_zoom);
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        let result = GameConfig::from_bytes(b"(world: (chunk_sise: 500.0))");
+        assert!(matches!(result, Err(ConfigError::Parse(_))));
+    }
+
+    #[test]
+    fn bad_values_name_their_field() {
+        let result = GameConfig::from_bytes(b"(world: (chunk_size: 0.0))");
+        assert_eq!(field_of(result), "world.chunk_size");
+        let result = GameConfig::from_bytes(b"(asteroid: (fracture_energy: 1.5))");
+        assert_eq!(field_of(result), "asteroid.fracture_energy");
+        let result = GameConfig::from_bytes(b"(camera: (min_zoom: 2.0, max_zoom: 1.0))");
+        assert_eq!(field_of(result), "camera.max_zoom");
+    }
+
+    #[test]
+    fn biome_shares_follow_weights() {
+        let config = BiomesConfig::default();
+        let total: f32 = config.biomes.iter().map(|biome| biome.weight).sum();
+        let mut counts: HashMap<Biome, usize> = HashMap::new();
+        let side = 300;
+        for x in -side / 2..side / 2 {
+            for y in -side / 2..side / 2 {
+                let biome = config.sample(7, x, y).unwrap();
+                *counts.entry(biome.kind).or_default() += 1;
+            }
+        }
+        for biome in &config.biomes {
+            let share = counts.get(&biome.kind).copied().unwrap_or(0) as f32 / (side * side) as f32;
+            let expected = biome.weight / total;
+            assert!(
+                (share - expected).abs() < 0.03,
+                "{:?}: share {} but weight says {}",
+                biome.kind,
+                share,
+                expected
+            );
+        }
+    }
+}
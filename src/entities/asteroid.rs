@@ -1,16 +1,30 @@
 use bevy::prelude::*;
+use bevy_prototype_lyon::{entity::ShapeBundle, prelude::*};
 use bevy_rapier2d::prelude::*;
 use lazy_static::lazy_static;
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use std::f32::consts::PI;
 
 use crate::components::chunk::Chunk;
-use crate::components::types::Asteroid;
-use crate::entities::entity::EntityBundle;
-use crate::util::from_polar;
+use crate::components::{
+    biome::Biome,
+    damage::{Category, Cracks, LastImpact},
+    difficulty::DifficultyScaling,
+    gravity::GravityWell,
+    material::Material,
+    types::Asteroid,
+};
+use crate::config::{AsteroidConfig, GameConfig, Shapes};
+use crate::entities::{
+    enemy::EnemyBundle,
+    entity::{EntityBundle, ShapeError},
+    gravity::GravityWellBundle,
+    station::StationBundle,
+};
+use crate::util::{from_polar, rotate};
 
 lazy_static! {
-    static ref ASTEROID_SHAPE: Vec<(f32, f32)> = vec![
+    pub static ref ASTEROID_SHAPE: Vec<(f32, f32)> = vec![
         (0.0, 0.0),
         (-1.0, -3.0),
         (-5.0, -2.0),
@@ -27,36 +41,184 @@ pub struct AsteroidBundle {
     base: EntityBundle,
 
     asteroid: Asteroid,
+    last_impact: LastImpact,
 }
 
 impl AsteroidBundle {
-    pub fn new(pos: RigidBodyPosition, vel: RigidBodyVelocity) -> AsteroidBundle {
-        let size = 4.0;
-        let asteroid_points = ASTEROID_SHAPE
-            .clone()
-            .into_iter()
+    pub fn new(
+        pos: RigidBodyPosition,
+        vel: RigidBodyVelocity,
+        material: Material,
+        config: &AsteroidConfig,
+        shape: &[(f32, f32)],
+        scale: f32,
+    ) -> Result<AsteroidBundle, ShapeError> {
+        let size = config.size * scale;
+        let asteroid_points = shape
+            .iter()
             .map(|p| (p.0 * size, p.1 * size))
             .collect::<Vec<(f32, f32)>>();
 
-        AsteroidBundle {
-            base: EntityBundle::new(asteroid_points, pos, vel, 25.0),
+        Self::from_outline(
+            asteroid_points,
+            pos,
+            vel,
+            material,
+            config.sturdiness * scale,
+        )
+    }
+
+    /// An asteroid with an outline already at its final size, such as a piece broken off another.
+    pub fn from_outline(
+        outline: Vec<(f32, f32)>,
+        pos: RigidBodyPosition,
+        vel: RigidBodyVelocity,
+        material: Material,
+        sturdiness: f32,
+    ) -> Result<AsteroidBundle, ShapeError> {
+        Ok(AsteroidBundle {
+            base: EntityBundle::new(outline, pos, vel, sturdiness, Category::Asteroid, material)?,
             asteroid: Asteroid {},
-        }
+            last_impact: LastImpact::default(),
+        })
     }
 
-    pub fn spawn_for_chunk(commands: &mut Commands, chunk: &Chunk) {
-        println!("Spawning for {:?}", chunk);
-        let mut rng = rand::thread_rng();
-        for _ in 0..2 {
-            let coords = chunk.random_point_inside(&mut rng);
-            let vel = from_polar(rng.gen_range(0.0..100.0), rng.gen_range(0.0..(2.0 * PI)));
-            commands.spawn_bundle(AsteroidBundle::new(
+    /// Fill a chunk with asteroids following its biome's rules, scaled by the difficulty.
+    /// Rare chunks also get a station.
+    pub fn spawn_for_chunk(
+        commands: &mut Commands,
+        chunk: &Chunk,
+        config: &GameConfig,
+        shapes: &Shapes,
+        seed: u64,
+        scaling: &DifficultyScaling,
+    ) {
+        let (x, y) = chunk.grid_coords(config.world.chunk_size);
+        let biome = match config.biomes.sample(seed, x, y) {
+            Some(biome) => biome,
+            None => return,
+        };
+        debug!("Spawning {:?} for {:?}", biome.kind, chunk);
+        let drift =
+            config.biomes.drift_direction(seed, x, y) * biome.drift * scaling.asteroid_speed;
+        let mut rng = StdRng::seed_from_u64(chunk.seed(seed));
+        let material = Material::pick(&mut rng, &biome.materials);
+        let size = config.world.chunk_size;
+
+        // Asteroids around a gravity well start out in orbit instead of drifting.
+        let gravity = &config.gravity;
+        let well = if biome.kind == Biome::GravityWell && rng.gen_bool(gravity.well_chance) {
+            let center = chunk.bottom_left() + Vec2::splat(size / 2.0);
+            let well = if rng.gen_bool(gravity.black_hole_chance) {
+                GravityWell::new(&gravity.black_hole, true)
+            } else {
+                GravityWell::new(&gravity.planetoid, false)
+            };
+            commands.spawn_bundle(GravityWellBundle::new(center, well.clone()));
+            Some((center, well))
+        } else {
+            None
+        };
+
+        let count = (biome.asteroids_per_chunk as f32 * scaling.asteroid_count).round() as u32;
+        for _ in 0..count {
+            let (coords, vel) = match &well {
+                Some((center, well)) => {
+                    let distance =
+                        rng.gen_range(well.radius * 2.0..(well.range / 2.0).max(well.radius * 3.0));
+                    let angle = rng.gen_range(0.0..(2.0 * PI));
+                    let offset = from_polar(distance, angle);
+                    // A quarter turn round from the offset, so every asteroid orbits the same way.
+                    let vel = from_polar(well.orbital_speed(distance), angle + PI / 2.0);
+                    (*center + offset, vel)
+                }
+                None => {
+                    let coords = chunk.random_point_inside(&mut rng, size);
+                    let vel = drift
+                        + from_polar(
+                            rng.gen_range(0.0..=biome.max_speed * scaling.asteroid_speed),
+                            rng.gen_range(0.0..(2.0 * PI)),
+                        );
+                    (coords, vel)
+                }
+            };
+            match AsteroidBundle::new(
                 coords.into(),
                 RigidBodyVelocity {
                     linvel: vel.into(),
                     angvel: rng.gen_range(-1.0..1.0),
                 },
-            ));
+                material,
+                &config.asteroid,
+                &shapes.asteroid,
+                rng.gen_range(biome.size.0..=biome.size.1),
+            ) {
+                Ok(asteroid) => {
+                    commands.spawn_bundle(asteroid);
+                }
+                Err(err) => warn!("Could not spawn asteroid: {}", err),
+            }
+        }
+        let enemy_chance = (biome.enemy_chance * scaling.enemy_chance as f64).min(1.0);
+        if rng.gen_bool(enemy_chance) {
+            let coords = chunk.random_point_inside(&mut rng, size);
+            match EnemyBundle::new(coords.into(), &config.enemy, shapes) {
+                Ok(enemy) => {
+                    commands.spawn_bundle(enemy);
+                }
+                Err(err) => warn!("Could not spawn enemy: {}", err),
+            }
+        }
+        if well.is_none() && rng.gen_bool(config.stations.chance) {
+            let center = chunk.bottom_left() + Vec2::splat(size / 2.0);
+            commands.spawn_bundle(StationBundle::new(center));
+        }
+    }
+}
+
+/// Most cracks an asteroid shows before it breaks.
+pub const MAX_CRACKS: u32 = 4;
+
+#[derive(Bundle)]
+pub struct CracksBundle {
+    #[bundle]
+    geometry: ShapeBundle,
+
+    cracks: Cracks,
+}
+
+impl CracksBundle {
+    /// `count` jagged lines running in from the edge of `outline`.
+    /// The same seed always draws the same cracks, so adding one doesn't move the others.
+    pub fn new(outline: &[Vec2], count: u32, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut builder = GeometryBuilder::new();
+        for _ in 0..count {
+            let i = rng.gen_range(0..outline.len());
+            let (a, b) = (outline[i], outline[(i + 1) % outline.len()]);
+            let mut point = a.lerp(b, rng.gen_range(0.2..0.8));
+            // Head roughly towards the center, a fifth of the way per segment.
+            let inward = -point * 0.2;
+            let mut points = vec![point];
+            for _ in 0..3 {
+                point += rotate(inward, rng.gen_range(-0.6..0.6));
+                points.push(point);
+            }
+            builder.add(&shapes::Polygon {
+                points,
+                closed: false,
+            });
+        }
+        Self {
+            geometry: builder.build(
+                ShapeColors {
+                    main: Color::GRAY,
+                    outline: Color::GRAY,
+                },
+                DrawMode::Stroke(StrokeOptions::default()),
+                Transform::from_xyz(0.0, 0.0, 0.1),
+            ),
+            cracks: Cracks { count },
         }
     }
 }
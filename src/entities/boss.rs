@@ -0,0 +1,240 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use super::entity::EntityBundle;
+use crate::components::{
+    boss::{Boss, BossKind, BossPart},
+    damage::Category,
+    material::Material,
+};
+use crate::config::BossConfig;
+use crate::util::from_polar;
+
+/// Weak points glow so the player knows where to aim.
+const WEAK_POINT_COLOR: Color = Color::ORANGE_RED;
+/// Distance between the centers of neighbouring worm segments.
+const WORM_SPACING: f32 = 60.0;
+
+/// Outline of a regular polygon around the origin.
+fn ring(radius: f32, sides: u32) -> Vec<(f32, f32)> {
+    (0..sides)
+        .map(|i| {
+            let p = from_polar(radius, i as f32 / sides as f32 * std::f32::consts::TAU);
+            (p.x, p.y)
+        })
+        .collect()
+}
+
+/// Outline of a rectangle spanning `min` to `max`.
+fn rect(min: (f32, f32), max: (f32, f32)) -> Vec<(f32, f32)> {
+    vec![min, (max.0, min.1), max, (min.0, max.1)]
+}
+
+/// Pin two parts together at a point on each, leaving them free to turn.
+fn ball_joint(commands: &mut Commands, a: Entity, anchor_a: Vec2, b: Entity, anchor_b: Vec2) {
+    let joint = BallJoint::new(
+        Point::new(anchor_a.x, anchor_a.y),
+        Point::new(anchor_b.x, anchor_b.y),
+    );
+    commands
+        .spawn()
+        .insert(JointBuilderComponent::new(joint, a, b));
+}
+
+/// Weld `b` to `a`, with `b`'s center held at `anchor` in `a`'s space.
+fn fixed_joint(commands: &mut Commands, a: Entity, anchor: Vec2, b: Entity) {
+    let joint = FixedJoint::new(
+        Isometry::translation(anchor.x, anchor.y),
+        Isometry::identity(),
+    );
+    commands
+        .spawn()
+        .insert(JointBuilderComponent::new(joint, a, b));
+}
+
+/// One body of a boss, with its own sturdiness.
+#[derive(Bundle)]
+pub struct BossPartBundle {
+    #[bundle]
+    base: EntityBundle,
+
+    part: BossPart,
+}
+
+impl BossPartBundle {
+    pub fn new(
+        shapes: &[Vec<(f32, f32)>],
+        position: Vec2,
+        boss: Entity,
+        weak_point: bool,
+        material: Material,
+        config: &BossConfig,
+    ) -> Self {
+        let (color, sturdiness) = if weak_point {
+            (WEAK_POINT_COLOR, config.weak_point)
+        } else {
+            (Color::WHITE, config.armor)
+        };
+        Self {
+            base: EntityBundle::compound(
+                shapes,
+                color,
+                position.into(),
+                RigidBodyVelocity::default(),
+                sturdiness,
+                Category::Enemy,
+                material,
+            )
+            .expect("boss parts are built from fixed, well-formed outlines")
+            .without_own_category_contacts(),
+            part: BossPart { boss, weak_point },
+        }
+    }
+
+    /// Assemble a boss with its core at `position`, and return the core.
+    pub fn spawn_boss(
+        commands: &mut Commands,
+        kind: BossKind,
+        position: Vec2,
+        config: &BossConfig,
+    ) -> Entity {
+        let core = commands.spawn().id();
+        let mut weak_points = 0;
+        let mut part = |commands: &mut Commands,
+                        shapes: &[Vec<(f32, f32)>],
+                        offset: Vec2,
+                        weak_point: bool,
+                        material: Material| {
+            if weak_point {
+                weak_points += 1;
+            }
+            commands
+                .spawn_bundle(Self::new(
+                    shapes,
+                    position + offset,
+                    core,
+                    weak_point,
+                    material,
+                    config,
+                ))
+                .id()
+        };
+
+        match kind {
+            // A chain of rocks trailing behind an armored head, with crystal weak points along
+            // its back and at the tip of its tail.
+            BossKind::Worm => {
+                commands.entity(core).insert_bundle(Self::new(
+                    &[ring(40.0, 7)],
+                    position,
+                    core,
+                    false,
+                    Material::Rock,
+                    config,
+                ));
+                let mut previous = core;
+                let segments = config.worm_segments + 1;
+                for i in 1..=segments {
+                    let tail = i == segments;
+                    let weak_point = tail || i % 3 == 0;
+                    let radius = if tail { 24.0 } else { 32.0 };
+                    let material = if weak_point {
+                        Material::Crystal
+                    } else {
+                        Material::Rock
+                    };
+                    let offset = Vec2::new(-WORM_SPACING * i as f32, 0.0);
+                    let segment = part(commands, &[ring(radius, 7)], offset, weak_point, material);
+                    let half = Vec2::new(WORM_SPACING / 2.0, 0.0);
+                    ball_joint(commands, previous, -half, segment, half);
+                    previous = segment;
+                }
+            }
+            // A hull built from several convex pieces, with hangars welded to either side.
+            BossKind::Carrier => {
+                commands.entity(core).insert_bundle(Self::new(
+                    &[
+                        rect((-70.0, -30.0), (70.0, 30.0)),
+                        vec![(70.0, -30.0), (110.0, 0.0), (70.0, 30.0)],
+                        rect((-100.0, -20.0), (-70.0, 20.0)),
+                    ],
+                    position,
+                    core,
+                    false,
+                    Material::Metal,
+                    config,
+                ));
+                for side in [-1.0, 1.0] {
+                    let hangar = Vec2::new(0.0, side * 44.0);
+                    let bay = part(
+                        commands,
+                        &[rect((-30.0, -12.0), (30.0, 12.0))],
+                        hangar,
+                        true,
+                        Material::Metal,
+                    );
+                    fixed_joint(commands, core, hangar, bay);
+                    let engine = Vec2::new(-88.0, side * 36.0);
+                    let pod = part(
+                        commands,
+                        &[rect((-14.0, -8.0), (14.0, 8.0))],
+                        engine,
+                        false,
+                        Material::Metal,
+                    );
+                    fixed_joint(commands, core, engine, pod);
+                }
+            }
+            // A rock torso with a crystal head and two swinging arms ending in crystal fists.
+            BossKind::Golem => {
+                commands.entity(core).insert_bundle(Self::new(
+                    &[ring(55.0, 6)],
+                    position,
+                    core,
+                    false,
+                    Material::Rock,
+                    config,
+                ));
+                let neck = Vec2::new(0.0, 80.0);
+                let head = part(commands, &[ring(22.0, 5)], neck, true, Material::Crystal);
+                fixed_joint(commands, core, neck, head);
+                for side in [-1.0, 1.0] {
+                    let shoulder = Vec2::new(side * 55.0, 0.0);
+                    let upper = part(
+                        commands,
+                        &[rect((-35.0, -12.0), (35.0, 12.0))],
+                        shoulder + Vec2::new(side * 35.0, 0.0),
+                        false,
+                        Material::Rock,
+                    );
+                    ball_joint(
+                        commands,
+                        core,
+                        shoulder,
+                        upper,
+                        Vec2::new(-side * 35.0, 0.0),
+                    );
+                    let fist = part(
+                        commands,
+                        &[ring(26.0, 6)],
+                        shoulder + Vec2::new(side * 96.0, 0.0),
+                        true,
+                        Material::Crystal,
+                    );
+                    ball_joint(
+                        commands,
+                        upper,
+                        Vec2::new(side * 35.0, 0.0),
+                        fist,
+                        Vec2::new(-side * 26.0, 0.0),
+                    );
+                }
+            }
+        }
+
+        commands
+            .entity(core)
+            .insert(Boss::new(kind, weak_points as f32 * config.weak_point));
+        core
+    }
+}
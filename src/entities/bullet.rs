@@ -2,12 +2,14 @@ use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 use lazy_static::lazy_static;
 
-use crate::components::types::*;
+use crate::components::{damage::*, material::Material, types::*};
 
-use super::entity::EntityBundle;
+use super::entity::{EntityBundle, ShapeError};
+use crate::config::{Shapes, WeaponConfig};
+use crate::util::rotate;
 
 lazy_static! {
-    static ref BULLET_SHAPE: Vec<(f32, f32)> =
+    pub static ref BULLET_SHAPE: Vec<(f32, f32)> =
         vec![(-1.0, -1.0), (-1.0, 1.0), (1.0, 1.0), (1.0, -1.0)];
 }
 
@@ -18,27 +20,53 @@ pub struct BulletBundle {
     launch_time: SpawnedAt,
     despawn_after: DespawnAfter,
     bullet: Bullet,
+    owner: Owner,
+    piercing: Piercing,
 }
 
 impl BulletBundle {
-    pub fn launch_from(pos: &RigidBodyPosition, vel: &RigidBodyVelocity, launch_time: f64) -> Self {
+    pub fn launch_from(
+        pos: &RigidBodyPosition,
+        vel: &RigidBodyVelocity,
+        hardpoint: (f32, f32),
+        launch_time: f64,
+        owner: Owner,
+        config: &WeaponConfig,
+        shapes: &Shapes,
+    ) -> Result<Self, ShapeError> {
         let angle = pos.position.rotation.angle();
-        let launch_vel = Vec2::new(-angle.sin(), angle.cos()) * 500.0 + vel.linvel.into();
+        let launch_vel =
+            Vec2::new(-angle.sin(), angle.cos()) * config.bullet_speed + vel.linvel.into();
         let ship_position: Vec2 = pos.position.translation.into();
-        let launch_position: Vec2 = ship_position + Vec2::new(-angle.sin(), angle.cos()) * 20.0;
-        Self {
+        let launch_position: Vec2 =
+            ship_position + rotate(Vec2::new(hardpoint.0, hardpoint.1), angle);
+        Ok(Self {
             base: EntityBundle::new(
-                BULLET_SHAPE.clone(),
+                shapes.bullet.clone(),
                 launch_position.into(),
                 RigidBodyVelocity {
                     linvel: launch_vel.into(),
                     angvel: 0.0,
                 },
                 1.0,
-            ),
+                Category::Bullet,
+                Material::Metal,
+            )?,
             launch_time: SpawnedAt(launch_time),
-            despawn_after: DespawnAfter(3.0),
+            despawn_after: DespawnAfter(config.bullet_lifetime),
             bullet: Bullet {},
+            owner,
+            piercing: Piercing(0),
+        })
+    }
+
+    /// Let the bullet pass through `hits` bodies before it is destroyed.
+    /// Piercing bullets don't push the bodies they pass through.
+    pub fn piercing(mut self, hits: u32) -> Self {
+        if hits > 0 {
+            self.base = self.base.without_solver_contacts();
         }
+        self.piercing = Piercing(hits);
+        self
     }
 }
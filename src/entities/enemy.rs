@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use super::{entity::ShapeError, ship::Ship};
+use crate::components::{damage::Category, types::Enemy};
+use crate::config::{EnemyConfig, Hull, Shapes};
+
+#[derive(Bundle)]
+pub struct EnemyBundle {
+    #[bundle]
+    ship: Ship,
+
+    enemy: Enemy,
+}
+
+impl EnemyBundle {
+    pub fn new(
+        pos: RigidBodyPosition,
+        config: &EnemyConfig,
+        shapes: &Shapes,
+    ) -> Result<Self, ShapeError> {
+        Ok(Self {
+            ship: Ship::new(
+                pos,
+                &Hull::from_shapes("Enemy", &config.ship, shapes),
+                Category::Enemy,
+            )?,
+            enemy: Enemy {},
+        })
+    }
+}
@@ -1,9 +1,85 @@
+use std::fmt;
+
 use bevy::prelude::*;
 use bevy_prototype_lyon::entity::ShapeBundle;
 use bevy_prototype_lyon::prelude::*;
 use bevy_rapier2d::prelude::*;
 
-use crate::components::types::*;
+use crate::components::{damage::Category, material::Material, types::*};
+use crate::util::{convex_parts, is_convex, is_self_intersecting, polygon_area};
+
+/// Why an outline can't be made into a body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShapeError {
+    TooFewPoints,
+    /// All the points lie on one line, so there's nothing inside the outline.
+    NoArea,
+    /// Two of the outline's edges cross each other.
+    SelfIntersecting,
+    /// The outline is concave and couldn't be cut into convex pieces, which happens when corners
+    /// lie right on the lines between other corners.
+    Unsplittable,
+}
+
+impl ShapeError {
+    pub fn reason(&self) -> &'static str {
+        match self {
+            ShapeError::TooFewPoints => "an outline needs at least 3 points",
+            ShapeError::NoArea => "an outline must enclose some area",
+            ShapeError::SelfIntersecting => "an outline's edges must not cross each other",
+            ShapeError::Unsplittable => {
+                "a concave outline must not have corners on the lines between other corners"
+            }
+        }
+    }
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.reason())
+    }
+}
+
+impl std::error::Error for ShapeError {}
+
+/// Check that an outline can be made into a collider.
+pub fn check_outline(points: &[(f32, f32)]) -> Result<(), ShapeError> {
+    convex_colliders(points).map(|_| ())
+}
+
+/// Check that an outline is a simple polygon with some area.
+fn check_simple(points: &[(f32, f32)]) -> Result<(), ShapeError> {
+    if points.len() < 3 {
+        Err(ShapeError::TooFewPoints)
+    } else if is_self_intersecting(points) {
+        Err(ShapeError::SelfIntersecting)
+    } else if polygon_area(points) <= f32::EPSILON {
+        Err(ShapeError::NoArea)
+    } else {
+        Ok(())
+    }
+}
+
+/// Convex colliders covering an outline: just its hull if it is already convex, or one for each
+/// convex piece it splits into if not.
+fn convex_colliders(points: &[(f32, f32)]) -> Result<Vec<ColliderShape>, ShapeError> {
+    check_simple(points)?;
+    let parts = if is_convex(points) {
+        vec![points.to_vec()]
+    } else {
+        convex_parts(points).ok_or(ShapeError::Unsplittable)?
+    };
+    parts
+        .iter()
+        .map(|part| {
+            let points = part
+                .iter()
+                .map(|(x, y)| Point::new(*x, *y))
+                .collect::<Vec<_>>();
+            ColliderShape::convex_hull(&points).ok_or(ShapeError::NoArea)
+        })
+        .collect()
+}
 
 #[derive(Bundle)]
 pub struct EntityBundle {
@@ -15,21 +91,33 @@ pub struct EntityBundle {
     collider: ColliderBundle,
     physics_sync: RigidBodyPositionSync,
     sturdiness: Sturdiness,
+    max_sturdiness: MaxSturdiness,
+    category: Category,
+    material: Material,
+    outline: Outline,
 }
 
 pub fn build_geometry(shape: &[(f32, f32)]) -> ShapeBundle {
-    GeometryBuilder::build_as(
-        &shapes::Polygon {
-            points: shape
-                .clone()
-                .into_iter()
-                .map(|(x, y)| Vec2::new(*x, *y))
-                .collect(),
+    build_colored_geometry(shape, Color::WHITE)
+}
+
+pub fn build_colored_geometry(shape: &[(f32, f32)], color: Color) -> ShapeBundle {
+    build_outlines_geometry(&[shape.to_vec()], color)
+}
+
+/// Several closed outlines drawn as one shape.
+pub fn build_outlines_geometry(outlines: &[Vec<(f32, f32)>], color: Color) -> ShapeBundle {
+    let mut builder = GeometryBuilder::new();
+    for outline in outlines.iter() {
+        builder.add(&shapes::Polygon {
+            points: outline.iter().map(|(x, y)| Vec2::new(*x, *y)).collect(),
             closed: true,
-        },
+        });
+    }
+    builder.build(
         ShapeColors {
-            main: Color::WHITE,
-            outline: Color::WHITE,
+            main: color,
+            outline: color,
         },
         DrawMode::Stroke(StrokeOptions::default()),
         Transform::default(),
@@ -37,14 +125,73 @@ pub fn build_geometry(shape: &[(f32, f32)]) -> ShapeBundle {
 }
 
 impl EntityBundle {
+    /// A body with any simple outline, convex or not.
     pub fn new(
         shape: Vec<(f32, f32)>,
         position: RigidBodyPosition,
         velocity: RigidBodyVelocity,
         sturdiness: f32,
-    ) -> Self {
-        EntityBundle {
-            geometry: build_geometry(&shape),
+        category: Category,
+        material: Material,
+    ) -> Result<Self, ShapeError> {
+        Self::from_outlines(
+            vec![shape],
+            Color::WHITE,
+            position,
+            velocity,
+            sturdiness,
+            category,
+            material,
+        )
+    }
+
+    /// A body made of several parts, given in its local space, drawn in one color.
+    pub fn compound(
+        parts: &[Vec<(f32, f32)>],
+        color: Color,
+        position: RigidBodyPosition,
+        velocity: RigidBodyVelocity,
+        sturdiness: f32,
+        category: Category,
+        material: Material,
+    ) -> Result<Self, ShapeError> {
+        Self::from_outlines(
+            parts.to_vec(),
+            color,
+            position,
+            velocity,
+            sturdiness,
+            category,
+            material,
+        )
+    }
+
+    fn from_outlines(
+        outlines: Vec<Vec<(f32, f32)>>,
+        color: Color,
+        position: RigidBodyPosition,
+        velocity: RigidBodyVelocity,
+        sturdiness: f32,
+        category: Category,
+        material: Material,
+    ) -> Result<Self, ShapeError> {
+        let mut colliders = Vec::new();
+        for outline in outlines.iter() {
+            colliders.extend(convex_colliders(outline)?);
+        }
+        let shape = if colliders.len() == 1 {
+            colliders.remove(0)
+        } else {
+            ColliderShape::compound(
+                colliders
+                    .into_iter()
+                    .map(|collider| (Isometry::identity(), collider))
+                    .collect(),
+            )
+        };
+        let props = material.props();
+        Ok(EntityBundle {
+            geometry: build_outlines_geometry(&outlines, color),
             body: RigidBodyBundle {
                 position,
                 velocity,
@@ -56,19 +203,51 @@ impl EntityBundle {
             },
             collider: ColliderBundle {
                 collider_type: ColliderType::Solid,
-                shape: ColliderShape::convex_hull(
-                    &(shape
-                        .clone()
-                        .into_iter()
-                        .map(|(x, y)| Point::new(x, y))
-                        .collect::<Vec<_>>()),
-                )
-                .unwrap(),
-                flags: ActiveEvents::CONTACT_EVENTS.into(),
+                shape,
+                material: ColliderMaterial {
+                    friction: props.friction,
+                    restitution: props.restitution,
+                    ..Default::default()
+                },
+                mass_properties: ColliderMassProps::Density(props.density),
+                flags: ColliderFlags {
+                    collision_groups: category.collision_groups(),
+                    solver_groups: category.solver_groups(),
+                    active_events: ActiveEvents::CONTACT_EVENTS,
+                    ..Default::default()
+                },
                 ..Default::default()
             },
             physics_sync: RigidBodyPositionSync::Discrete,
             sturdiness: Sturdiness(sturdiness),
+            max_sturdiness: MaxSturdiness(sturdiness),
+            category,
+            material,
+            outline: Outline(outlines),
+        })
+    }
+
+    /// Set the density so the body's total mass is `mass`, whatever its size.
+    pub fn with_mass(mut self, mass: f32) -> Self {
+        let area = self.collider.shape.mass_properties(1.0).inv_mass.recip();
+        self.collider.mass_properties = ColliderMassProps::Density(mass / area);
+        self
+    }
+
+    /// Keep detecting contacts, but stop the solver from pushing other bodies on impact.
+    pub fn without_solver_contacts(mut self) -> Self {
+        self.collider.flags.solver_groups = InteractionGroups::none();
+        self
+    }
+
+    /// Ignore other bodies of the same category, so parts of one jointed body don't grind on
+    /// each other.
+    pub fn without_own_category_contacts(mut self) -> Self {
+        let flags = &mut self.collider.flags;
+        for groups in [&mut flags.collision_groups, &mut flags.solver_groups] {
+            *groups =
+                InteractionGroups::new(groups.memberships, groups.filter & !groups.memberships);
         }
+        self
     }
 }
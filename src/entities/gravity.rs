@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::{entity::ShapeBundle, prelude::*};
+use bevy_rapier2d::prelude::*;
+
+use crate::components::{
+    damage::Category, gravity::GravityWell, material::Material, types::Sturdiness,
+};
+
+/// A planetoid or black hole: an immovable body that pulls others towards it.
+#[derive(Bundle)]
+pub struct GravityWellBundle {
+    #[bundle]
+    geometry: ShapeBundle,
+    #[bundle]
+    body: RigidBodyBundle,
+    #[bundle]
+    collider: ColliderBundle,
+    physics_sync: RigidBodyPositionSync,
+    category: Category,
+    material: Material,
+    sturdiness: Sturdiness,
+    well: GravityWell,
+}
+
+impl GravityWellBundle {
+    /// Planetoids are solid. Black holes let bodies through so they can be swallowed.
+    pub fn new(position: Vec2, well: GravityWell) -> Self {
+        let color = if well.swallows {
+            Color::PURPLE
+        } else {
+            Color::GRAY
+        };
+        // Collides like an asteroid, so everything that hits asteroids bounces off it too.
+        let category = Category::Asteroid;
+        Self {
+            geometry: GeometryBuilder::build_as(
+                &shapes::Circle {
+                    radius: well.radius,
+                    center: Vec2::ZERO,
+                },
+                ShapeColors {
+                    main: color,
+                    outline: color,
+                },
+                DrawMode::Stroke(StrokeOptions::default()),
+                Transform::default(),
+            ),
+            body: RigidBodyBundle {
+                body_type: RigidBodyType::Static,
+                position: position.into(),
+                ..Default::default()
+            },
+            collider: ColliderBundle {
+                collider_type: if well.swallows {
+                    ColliderType::Sensor
+                } else {
+                    ColliderType::Solid
+                },
+                shape: ColliderShape::ball(well.radius),
+                flags: ColliderFlags {
+                    collision_groups: category.collision_groups(),
+                    solver_groups: category.solver_groups(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            physics_sync: RigidBodyPositionSync::Discrete,
+            category,
+            material: Material::Rock,
+            // Nothing can wear a well down, but it still takes part in collisions.
+            sturdiness: Sturdiness(f32::INFINITY),
+            well,
+        }
+    }
+}
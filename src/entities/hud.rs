@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::ShapeBundle;
+
+use super::entity::build_colored_geometry;
+use crate::components::{
+    hud::{ThreatIndicator, Urgency},
+    mission::WaypointMarker,
+};
+
+/// Points up; rotated to face the threat.
+const ARROW_SHAPE: [(f32, f32); 3] = [(-6.0, -4.0), (0.0, 8.0), (6.0, -4.0)];
+
+#[derive(Bundle)]
+pub struct ThreatIndicatorBundle {
+    #[bundle]
+    geometry: ShapeBundle,
+
+    indicator: ThreatIndicator,
+}
+
+impl ThreatIndicatorBundle {
+    /// A hidden indicator, shown when a threat of this urgency is assigned to it.
+    pub fn new(urgency: Urgency) -> Self {
+        let mut geometry = build_colored_geometry(&ARROW_SHAPE, urgency.color());
+        geometry.transform.translation.z = 2.0;
+        geometry.visible.is_visible = false;
+        Self {
+            geometry,
+            indicator: ThreatIndicator { urgency },
+        }
+    }
+}
+
+#[derive(Bundle)]
+pub struct WaypointMarkerBundle {
+    #[bundle]
+    geometry: ShapeBundle,
+
+    marker: WaypointMarker,
+}
+
+impl WaypointMarkerBundle {
+    /// A hidden marker, shown while the current mission has a waypoint.
+    pub fn new(color: Color) -> Self {
+        let mut geometry = build_colored_geometry(&ARROW_SHAPE, color);
+        geometry.transform.translation.z = 2.0;
+        geometry.visible.is_visible = false;
+        Self {
+            geometry,
+            marker: WaypointMarker {},
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use lazy_static::lazy_static;
+
+use super::entity::{EntityBundle, ShapeError};
+use crate::components::{damage::Category, material::Material, mission::CargoDrone};
+
+lazy_static! {
+    pub static ref DRONE_SHAPE: Vec<(f32, f32)> = vec![
+        (-8.0, -10.0),
+        (-8.0, 6.0),
+        (0.0, 12.0),
+        (8.0, 6.0),
+        (8.0, -10.0),
+    ];
+}
+
+/// A cargo drone to be escorted to `destination`.
+/// Flies on the player's side, so the player's bullets pass it by.
+#[derive(Bundle)]
+pub struct CargoDroneBundle {
+    #[bundle]
+    base: EntityBundle,
+
+    drone: CargoDrone,
+}
+
+impl CargoDroneBundle {
+    pub fn new(position: Vec2, destination: Vec2, sturdiness: f32) -> Result<Self, ShapeError> {
+        Ok(Self {
+            base: EntityBundle::new(
+                DRONE_SHAPE.clone(),
+                position.into(),
+                RigidBodyVelocity::default(),
+                sturdiness,
+                Category::Ship,
+                Material::Metal,
+            )?,
+            drone: CargoDrone { destination },
+        })
+    }
+}
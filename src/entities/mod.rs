@@ -1,4 +1,14 @@
 pub mod asteroid;
+pub mod boss;
 pub mod bullet;
+pub mod enemy;
 pub mod entity;
+pub mod gravity;
+pub mod hud;
+pub mod mission;
+pub mod particle;
+pub mod pickup;
+pub mod shield;
 pub mod ship;
+pub mod starfield;
+pub mod station;
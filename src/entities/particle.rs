@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::ShapeBundle;
+
+use super::entity::build_colored_geometry;
+use crate::components::particle::*;
+
+#[derive(Bundle)]
+pub struct ParticleBundle {
+    #[bundle]
+    geometry: ShapeBundle,
+
+    particle: Particle,
+}
+
+impl ParticleBundle {
+    /// An idle particle, hidden until it is emitted.
+    pub fn new(kind: ParticleKind) -> Self {
+        let mut geometry = build_colored_geometry(&kind.shape(), kind.color());
+        geometry.visible.is_visible = false;
+        Self {
+            geometry,
+            particle: Particle {
+                kind,
+                velocity: Vec2::ZERO,
+                age: 0.0,
+                lifetime: 0.0,
+            },
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::{entity::ShapeBundle, prelude::*};
+use bevy_rapier2d::prelude::*;
+
+use crate::components::{
+    damage::Category,
+    pickup::{Pickup, PickupKind},
+    types::{DespawnAfter, SpawnedAt},
+};
+
+const PICKUP_SIZE: f32 = 6.0;
+
+/// A floating pickup. Its sensor collider only detects the player's ship.
+#[derive(Bundle)]
+pub struct PickupBundle {
+    #[bundle]
+    geometry: ShapeBundle,
+    #[bundle]
+    body: RigidBodyBundle,
+    #[bundle]
+    collider: ColliderBundle,
+    physics_sync: RigidBodyPositionSync,
+    category: Category,
+    pickup: Pickup,
+    spawned_at: SpawnedAt,
+    despawn_after: DespawnAfter,
+}
+
+impl PickupBundle {
+    pub fn new(
+        position: Vec2,
+        velocity: Vec2,
+        kind: PickupKind,
+        amount: f32,
+        spawn_time: f64,
+        lifetime: f64,
+    ) -> Self {
+        let color = kind.color();
+        let category = Category::Pickup;
+        Self {
+            geometry: GeometryBuilder::build_as(
+                &shapes::Polygon {
+                    points: vec![
+                        Vec2::new(0.0, PICKUP_SIZE),
+                        Vec2::new(PICKUP_SIZE, 0.0),
+                        Vec2::new(0.0, -PICKUP_SIZE),
+                        Vec2::new(-PICKUP_SIZE, 0.0),
+                    ],
+                    closed: true,
+                },
+                ShapeColors {
+                    main: color,
+                    outline: color,
+                },
+                DrawMode::Stroke(StrokeOptions::default()),
+                Transform::default(),
+            ),
+            body: RigidBodyBundle {
+                position: position.into(),
+                velocity: RigidBodyVelocity {
+                    linvel: velocity.into(),
+                    angvel: 2.0,
+                },
+                forces: RigidBodyForces {
+                    gravity_scale: 0.0,
+                    ..Default::default()
+                },
+                // Drops drift to a stop rather than flying off.
+                damping: RigidBodyDamping {
+                    linear_damping: 1.0,
+                    angular_damping: 0.0,
+                },
+                ..Default::default()
+            },
+            collider: ColliderBundle {
+                collider_type: ColliderType::Sensor,
+                shape: ColliderShape::ball(PICKUP_SIZE),
+                flags: ColliderFlags {
+                    collision_groups: category.collision_groups(),
+                    solver_groups: category.solver_groups(),
+                    active_events: ActiveEvents::INTERSECTION_EVENTS,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            physics_sync: RigidBodyPositionSync::Discrete,
+            category,
+            pickup: Pickup { kind, amount },
+            spawned_at: SpawnedAt(spawn_time),
+            despawn_after: DespawnAfter(lifetime),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::{entity::ShapeBundle, prelude::*};
+
+use crate::components::shield::{ShieldBubble, SHIELD_STEPS};
+
+const MAX_OPACITY: f32 = 0.8;
+
+/// Outline of a shield of the given radius, drawn more opaque the higher its `step`.
+/// Hidden when the shield is down.
+pub fn build_bubble(radius: f32, step: u32) -> ShapeBundle {
+    let color = Color::rgba(
+        0.4,
+        0.7,
+        1.0,
+        MAX_OPACITY * step as f32 / SHIELD_STEPS as f32,
+    );
+    let mut bubble = GeometryBuilder::build_as(
+        &shapes::Circle {
+            radius,
+            center: Vec2::ZERO,
+        },
+        ShapeColors {
+            main: color,
+            outline: color,
+        },
+        DrawMode::Stroke(StrokeOptions::default()),
+        Transform::from_xyz(0.0, 0.0, 0.1),
+    );
+    bubble.visible = Visible {
+        is_visible: step > 0,
+        is_transparent: true,
+    };
+    bubble
+}
+
+#[derive(Bundle)]
+pub struct ShieldBubbleBundle {
+    #[bundle]
+    geometry: ShapeBundle,
+
+    bubble: ShieldBubble,
+}
+
+impl ShieldBubbleBundle {
+    pub fn new(radius: f32) -> Self {
+        Self {
+            geometry: build_bubble(radius, SHIELD_STEPS),
+            bubble: ShieldBubble { step: SHIELD_STEPS },
+        }
+    }
+}
@@ -3,13 +3,40 @@ use bevy_prototype_lyon::entity::ShapeBundle;
 use bevy_rapier2d::prelude::*;
 use lazy_static::lazy_static;
 
-use super::entity::EntityBundle;
+use super::entity::{build_geometry, EntityBundle, ShapeError};
 
-use crate::components::ship::*;
+use crate::components::{
+    damage::{Category, Condition},
+    material::Material,
+    particle::ParticleEmitter,
+    ship::*,
+};
+use crate::config::Hull;
 
 lazy_static! {
-    static ref SPACESHIP_SHAPE: Vec<(f32, f32)> = vec![(-6.0, -10.0), (0.0, 14.0), (6.0, -10.0),];
-    pub static ref EXHAUST_SHAPE: Vec<(f32, f32)> = vec![(-3.0, -12.0), (0.0, -20.0), (3.0, -12.0)];
+    pub static ref SPACESHIP_SHAPE: Vec<(f32, f32)> =
+        vec![(-6.0, -10.0), (0.0, 14.0), (6.0, -10.0),];
+    pub static ref EXHAUST_SHAPE: Vec<(f32, f32)> = vec![(-3.0, 0.0), (0.0, -8.0), (3.0, 0.0)];
+    pub static ref SPACESHIP_THRUSTERS: Vec<ThrusterMount> = vec![
+        ThrusterMount {
+            kind: ThrusterKind::Main,
+            position: (0.0, -12.0),
+            angle: 0.0,
+            scale: 1.0,
+        },
+        ThrusterMount {
+            kind: ThrusterKind::RotateLeft,
+            position: (1.5, 8.0),
+            angle: std::f32::consts::FRAC_PI_2,
+            scale: 0.4,
+        },
+        ThrusterMount {
+            kind: ThrusterKind::RotateRight,
+            position: (-1.5, 8.0),
+            angle: -std::f32::consts::FRAC_PI_2,
+            scale: 0.4,
+        },
+    ];
 }
 
 #[derive(Bundle)]
@@ -20,6 +47,26 @@ pub struct ThrusterBundle {
     thruster: Thruster,
 }
 
+impl ThrusterBundle {
+    /// A hidden flame at the given mount, to be added as a child of its ship.
+    pub fn new(mount: &ThrusterMount, shape: &[(f32, f32)]) -> Self {
+        let mut base = build_geometry(shape);
+        base.transform = Transform {
+            translation: Vec3::new(mount.position.0, mount.position.1, -0.1),
+            rotation: Quat::from_rotation_z(mount.angle),
+            scale: Vec3::new(mount.scale, 0.0, 1.0),
+        };
+        base.visible.is_visible = false;
+        Self {
+            base,
+            thruster: Thruster {
+                mount: mount.clone(),
+                level: 0.0,
+            },
+        }
+    }
+}
+
 #[derive(Bundle)]
 pub struct Ship {
     #[bundle]
@@ -28,26 +75,56 @@ pub struct Ship {
     engines: Engines,
     controls: Controls,
     fuel: Fuel,
+    exhaust: ParticleEmitter,
+    weapon: Weapon,
+    hardpoints: Hardpoints,
+    thrusters: ThrusterMounts,
+    condition: Condition,
 }
 
 impl Ship {
-    pub fn new(pos: RigidBodyPosition) -> Self {
-        Self {
+    pub fn new(
+        pos: RigidBodyPosition,
+        hull: &Hull,
+        category: Category,
+    ) -> Result<Self, ShapeError> {
+        let mut exhaust = ParticleEmitter::exhaust();
+        let main_thrusters = hull
+            .thrusters
+            .iter()
+            .filter(|mount| mount.kind == ThrusterKind::Main)
+            .map(|mount| Vec2::new(mount.position.0, mount.position.1))
+            .collect::<Vec<_>>();
+        if !main_thrusters.is_empty() {
+            let center = main_thrusters.iter().fold(Vec2::ZERO, |sum, p| sum + *p)
+                / main_thrusters.len() as f32;
+            exhaust.offset = center + Vec2::new(0.0, -2.0);
+        }
+
+        Ok(Self {
             base: EntityBundle::new(
-                SPACESHIP_SHAPE.clone(),
+                hull.shape.clone(),
                 pos,
                 RigidBodyVelocity {
                     linvel: Vec2::ZERO.into(),
                     angvel: 0.0,
                 },
-                100.0,
-            ),
+                hull.ship.sturdiness,
+                category,
+                Material::Metal,
+            )?
+            .with_mass(hull.mass),
             engines: Engines {
-                thrust: 50000.0,
-                spin: 5.0,
+                thrust: hull.ship.thrust,
+                spin: hull.ship.spin,
             },
             controls: Default::default(),
-            fuel: Fuel(100.0),
-        }
+            fuel: Fuel(hull.ship.fuel),
+            exhaust,
+            weapon: Default::default(),
+            hardpoints: Hardpoints(hull.hardpoints.clone()),
+            thrusters: ThrusterMounts(hull.thrusters.clone()),
+            condition: Condition::Intact,
+        })
     }
 }
@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::{entity::ShapeBundle, prelude::*};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::components::starfield::LayerContents;
+
+const NEBULA_COLORS: [(f32, f32, f32); 3] = [(0.5, 0.2, 0.7), (0.1, 0.5, 0.6), (0.6, 0.2, 0.25)];
+
+/// Everything in one chunk of a background layer as a single mesh, with its origin at the
+/// chunk's bottom left corner. `None` if the chunk is empty.
+pub fn build_layer_chunk(contents: &LayerContents, size: f32, seed: u64) -> Option<ShapeBundle> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut builder = GeometryBuilder::new();
+    let color = match contents {
+        LayerContents::Stars(layer) => {
+            let half = layer.size / 2.0;
+            for _ in 0..layer.stars_per_chunk {
+                let center = Vec2::new(rng.gen_range(0.0..size), rng.gen_range(0.0..size));
+                builder.add(&shapes::Polygon {
+                    points: vec![
+                        center + Vec2::new(-half, -half),
+                        center + Vec2::new(-half, half),
+                        center + Vec2::new(half, half),
+                        center + Vec2::new(half, -half),
+                    ],
+                    closed: true,
+                });
+            }
+            let b = layer.brightness;
+            Color::rgb(b, b, b)
+        }
+        LayerContents::Nebula(nebula) => {
+            if !rng.gen_bool(nebula.chance) {
+                return None;
+            }
+            // A few overlapping circles, so clouds aren't perfectly round.
+            let center = Vec2::new(rng.gen_range(0.0..size), rng.gen_range(0.0..size));
+            let radius = rng.gen_range(nebula.min_radius..=nebula.max_radius);
+            for _ in 0..3 {
+                let offset = Vec2::new(rng.gen_range(-0.5..0.5), rng.gen_range(-0.5..0.5));
+                builder.add(&shapes::Circle {
+                    radius: radius * rng.gen_range(0.6..1.0),
+                    center: center + offset * radius,
+                });
+            }
+            let (r, g, b) = NEBULA_COLORS[rng.gen_range(0..NEBULA_COLORS.len())];
+            Color::rgba(r, g, b, nebula.opacity)
+        }
+    };
+    let mut bundle = builder.build(
+        ShapeColors {
+            main: color,
+            outline: color,
+        },
+        DrawMode::Fill(FillOptions::default()),
+        Transform::default(),
+    );
+    bundle.visible.is_transparent = true;
+    Some(bundle)
+}
@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::{entity::ShapeBundle, prelude::*};
+use bevy_rapier2d::prelude::*;
+
+use crate::components::{
+    damage::Category, material::Material, station::Station, types::Sturdiness,
+};
+use crate::util::from_polar;
+
+pub const STATION_RADIUS: f32 = 90.0;
+/// Distance from the station's edge to the center of its docking zone.
+const PORT_OFFSET: f32 = 40.0;
+
+/// An immovable station: an octagonal hull with a docking bay marked out above it.
+#[derive(Bundle)]
+pub struct StationBundle {
+    #[bundle]
+    geometry: ShapeBundle,
+    #[bundle]
+    body: RigidBodyBundle,
+    #[bundle]
+    collider: ColliderBundle,
+    physics_sync: RigidBodyPositionSync,
+    category: Category,
+    material: Material,
+    sturdiness: Sturdiness,
+    station: Station,
+}
+
+impl StationBundle {
+    pub fn new(position: Vec2) -> Self {
+        let octagon = |radius: f32| shapes::Polygon {
+            points: (0..8)
+                .map(|i| from_polar(radius, (i as f32 + 0.5) * std::f32::consts::FRAC_PI_4))
+                .collect(),
+            closed: true,
+        };
+        let port = Vec2::new(0.0, STATION_RADIUS + PORT_OFFSET);
+        let mut builder = GeometryBuilder::new();
+        builder.add(&octagon(STATION_RADIUS));
+        builder.add(&octagon(STATION_RADIUS * 0.6));
+        // Brackets either side of the docking zone.
+        for side in [-1.0, 1.0] {
+            builder.add(&shapes::Polygon {
+                points: vec![
+                    port + Vec2::new(side * 20.0, -20.0),
+                    port + Vec2::new(side * 30.0, -20.0),
+                    port + Vec2::new(side * 30.0, 20.0),
+                    port + Vec2::new(side * 20.0, 20.0),
+                ],
+                closed: false,
+            });
+        }
+        // Collides like an asteroid, so ships and bullets bounce off it.
+        let category = Category::Asteroid;
+        Self {
+            geometry: builder.build(
+                ShapeColors {
+                    main: Color::TEAL,
+                    outline: Color::TEAL,
+                },
+                DrawMode::Stroke(StrokeOptions::default()),
+                Transform::default(),
+            ),
+            body: RigidBodyBundle {
+                body_type: RigidBodyType::Static,
+                position: position.into(),
+                ..Default::default()
+            },
+            collider: ColliderBundle {
+                shape: ColliderShape::ball(STATION_RADIUS),
+                flags: ColliderFlags {
+                    collision_groups: category.collision_groups(),
+                    solver_groups: category.solver_groups(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            physics_sync: RigidBodyPositionSync::Discrete,
+            category,
+            material: Material::Metal,
+            // Stations can't be destroyed, but still take part in collisions.
+            sturdiness: Sturdiness(f32::INFINITY),
+            station: Station {
+                port: position + port,
+            },
+        }
+    }
+}
@@ -1,18 +1,46 @@
+// Bevy systems take everything they touch as arguments, so long parameter lists and nested
+// query types come with the territory. The `Bundle` derive forgets each bundle once it has
+// moved the components out.
+#![allow(
+    clippy::too_many_arguments,
+    clippy::type_complexity,
+    clippy::forget_non_drop
+)]
+
 mod components;
+mod config;
 mod entities;
+mod progression;
 mod systems;
 mod util;
 
-use bevy::prelude::*;
+use bevy::{diagnostic::FrameTimeDiagnosticsPlugin, prelude::*};
 use bevy_prototype_lyon::prelude::*;
 use bevy_rapier2d::prelude::*;
-use components::chunk::{Chunk, SpawnedChunks};
-use components::types::{Score, ScoreText};
+use components::boss::BossEncounters;
+use components::camera::CameraController;
+use components::chunk::{SpawnedChunks, WorldSeed};
+use components::console::{CommandRegistry, Console, ConsoleCommandsExt, GodMode, TimeScale};
+use components::damage::DamageRules;
+use components::debug::DebugOverlay;
+use components::difficulty::Difficulty;
+use components::events::{DestroyedEvent, ImpactEvent};
+use components::hud::Lives;
+use components::mission::Missions;
+use components::particle::{ParticleBurst, ParticlePool};
+use components::pickup::Credits;
+use components::station::Docking;
+use components::types::Score;
 use wasm_bindgen::prelude::*;
 
-use crate::components::types::{LastAsteroidSpawnTime, Player};
-use crate::entities::{asteroid::*, ship::*};
-use crate::systems::{common::*, player::*, ship::*};
+use crate::components::types::{AppState, SelectedHull};
+use crate::config::{ConfigLoader, GameConfig, HullCatalogue, HullsLoader, Shapes, ShapesLoader};
+use crate::progression::Progression;
+use crate::systems::{
+    biome::*, boss::*, camera::*, common::*, condition::*, config::*, console::*, debug::*,
+    economy::*, enemy::*, fracture::*, gravity::*, hud::*, mission::*, particle::*, pickup::*,
+    player::*, run::*, shield::*, ship::*, starfield::*, station::*,
+};
 
 #[wasm_bindgen]
 pub fn run() {
@@ -23,66 +51,180 @@ pub fn run() {
     #[cfg(target_arch = "wasm32")]
     app.add_plugin(bevy_webgl2::WebGL2Plugin);
 
+    // the Web has no asset watcher, so debug builds reload tuning on a key press
+    #[cfg(all(debug_assertions, target_arch = "wasm32"))]
+    app.add_system(manual_reload.system());
+
     app.insert_resource(ClearColor(Color::BLACK))
         .add_plugin(ShapePlugin)
         .add_plugin(RapierPhysicsPlugin::<NoUserData>::default())
+        .add_plugin(FrameTimeDiagnosticsPlugin)
+        .add_asset::<GameConfig>()
+        .init_asset_loader::<ConfigLoader>()
+        .init_resource::<GameConfig>()
+        .add_asset::<Shapes>()
+        .init_asset_loader::<ShapesLoader>()
+        .init_resource::<Shapes>()
+        .add_asset::<HullCatalogue>()
+        .init_asset_loader::<HullsLoader>()
+        .init_resource::<HullCatalogue>()
+        .add_state(AppState::Loading)
         .add_startup_system(setup.system())
-        .add_system(impulse.system())
-        .add_system(player.system())
-        .add_system(display_score.system())
-        .add_system(weapons.system())
-        .add_system(despawn.system())
+        .add_startup_system(load_config.system())
+        .add_startup_system(spawn_debug_text.system())
+        .add_startup_system(spawn_console_text.system())
+        .add_startup_system(spawn_particle_pool.system())
+        .add_startup_system(spawn_hull_warning.system())
+        .add_startup_system(spawn_hud.system())
+        .add_startup_system(spawn_threat_indicators.system())
+        .add_startup_system(spawn_waypoint_marker.system())
+        .add_system_set(
+            SystemSet::on_update(AppState::Loading).with_system(wait_for_config.system()),
+        )
+        .add_system_set(
+            SystemSet::on_exit(AppState::Loading)
+                .with_system(spawn_background_layers.system())
+                .with_system(apply_default_difficulty.system()),
+        )
+        .add_system_set(
+            SystemSet::on_enter(AppState::HullSelect).with_system(spawn_hull_select.system()),
+        )
+        .add_system_set(
+            SystemSet::on_update(AppState::HullSelect).with_system(hull_select.system()),
+        )
+        .add_system_set(
+            SystemSet::on_exit(AppState::HullSelect).with_system(despawn_hull_select.system()),
+        )
+        .add_system_set(
+            SystemSet::on_enter(AppState::InGame)
+                .with_system(reset_world.exclusive_system())
+                .with_system(spawn_player.system())
+                .with_system(reset_missions.system()),
+        )
+        .add_system_set(
+            SystemSet::on_update(AppState::InGame)
+                .with_system(award_score.system())
+                .with_system(end_run.system())
+                .with_system(dock.system())
+                .with_system(offer_missions.system())
+                .with_system(escort_drones.system())
+                .with_system(track_missions.system())
+                .with_system(spawn_bosses.system())
+                .with_system(boss_ai.system()),
+        )
+        // The rest of the simulation, which stands still with physics while docked.
+        .add_system_set(
+            SystemSet::on_update(AppState::InGame)
+                .with_system(impulse.system())
+                .with_system(gravity.system())
+                .with_system(recharge_shields.system())
+                .with_system(player.system())
+                .with_system(weapons.system())
+                .with_system(magnetize_pickups.system())
+                .with_system(collect_pickups.system())
+                .with_system(tick_power_ups.system())
+                .with_system(despawn.system())
+                .with_system(enemy_ai.system()),
+        )
+        .add_system_set(SystemSet::on_enter(AppState::Docked).with_system(enter_station.system()))
+        .add_system_set(SystemSet::on_update(AppState::Docked).with_system(station_menu.system()))
+        .add_system_set(SystemSet::on_exit(AppState::Docked).with_system(leave_station.system()))
+        .add_system(apply_config_changes.system())
+        .add_system(attach_thrusters.system())
+        .add_system(animate_thrusters.system())
+        .add_system(attach_shield_bubbles.system())
+        .add_system(shield_visuals.system())
+        .add_system(asteroid_cracks.system())
+        .add_system(ship_condition.system())
+        .add_system(critical_ship_effects.system())
+        .add_system(hull_warning.system())
+        .add_system(hud_bars.system())
+        .add_system(hud_text.system())
+        .add_system(minimap.system())
+        .add_system(threat_indicators.system())
+        .add_system(waypoint_marker.system())
+        .add_system(boss_bar.system())
+        .add_system(drop_loot.system())
+        .add_system(drop_ore.system())
+        .add_system(fracture_asteroids.system())
         .add_system(damage.system())
         .add_system(health.system())
-        .add_system(camera_tracking.system())
+        .add_system(camera_shake.system())
+        .add_system(camera_controller.system())
+        .add_system(update_background.system())
+        .add_system(biome_background.system())
         .add_system(spawn_asteroids.system())
         .add_system(mock_touch.system())
-        .init_resource::<LastAsteroidSpawnTime>()
+        .add_system(toggle_debug_overlay.system())
+        .add_system(debug_text.system())
+        .add_system(debug_shapes.system())
+        .add_system(console_input.system())
+        .add_system(console_text.system())
+        .add_system(run_console_commands.exclusive_system())
+        .add_system(apply_time_scale.system())
+        .add_system(impact_effects.system())
+        .add_system(destruction_effects.system())
+        .add_system(emit_particles.system())
+        .add_system(update_particles.system())
+        .add_console_command(
+            "spawn",
+            "spawn <asteroid|enemy|pickup> at the cursor",
+            spawn_command,
+        )
+        .add_console_command(
+            "seed",
+            "seed <n>: change the world seed and regenerate",
+            seed_command,
+        )
+        .add_console_command(
+            "timescale",
+            "timescale <scale>: speed up or slow down physics",
+            time_scale_command,
+        )
+        .add_console_command("score", "score <n>: set the score", score_command)
+        .add_console_command("fuel", "refill the player's fuel", fuel_command)
+        .add_console_command("god", "toggle god mode", god_command)
+        .add_console_command(
+            "teleport",
+            "teleport <x> <y>: jump to a chunk",
+            teleport_command,
+        )
+        .add_console_command(
+            "goto",
+            "goto <x> <y>: jump to a point in the world",
+            goto_command,
+        )
         .init_resource::<SpawnedChunks>()
         .init_resource::<Score>()
+        .init_resource::<Lives>()
+        .init_resource::<Difficulty>()
+        .init_resource::<Credits>()
+        .init_resource::<Docking>()
+        .init_resource::<Missions>()
+        .init_resource::<BossEncounters>()
+        .init_resource::<DamageRules>()
+        .init_resource::<DebugOverlay>()
+        .init_resource::<WorldSeed>()
+        .init_resource::<Console>()
+        .init_resource::<CommandRegistry>()
+        .init_resource::<GodMode>()
+        .init_resource::<TimeScale>()
+        .init_resource::<ParticlePool>()
+        .init_resource::<SelectedHull>()
+        .insert_resource(Progression::load())
+        .add_event::<ImpactEvent>()
+        .add_event::<DestroyedEvent>()
+        .add_event::<ParticleBurst>()
         .run();
 }
 
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn setup(mut commands: Commands) {
     // cameras
-    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
-    commands.spawn_bundle(UiCameraBundle::default());
-
     commands
-        .spawn_bundle(Ship::new(Vec2::new(0.0, -215.0).into()))
-        .insert(Player {});
+        .spawn_bundle(OrthographicCameraBundle::new_2d())
+        .insert(CameraController::default());
+    commands.spawn_bundle(UiCameraBundle::default());
 
     // commands.spawn_bundle(AsteroidBundle::new(Default::default(), Default::default()));
     // AsteroidBundle::spawn_for_chunk(&mut commands, &Chunk::new(0.0, 0.0));
-
-    commands
-        .spawn_bundle(TextBundle {
-            style: Style {
-                align_self: AlignSelf::FlexEnd,
-                position_type: PositionType::Absolute,
-                position: Rect {
-                    top: Val::Px(5.0),
-                    left: Val::Px(15.0),
-                    ..Default::default()
-                },
-                ..Default::default()
-            },
-            // Use the `Text::with_section` constructor
-            text: Text::with_section(
-                // Accepts a `String` or any type that converts into a `String`, such as `&str`
-                "hello\nbevy!",
-                TextStyle {
-                    font_size: 32.0,
-                    color: Color::WHITE,
-                    font: asset_server.load("FiraSans-Bold.ttf"),
-                },
-                // Note: You can use `Default::default()` in place of the `TextAlignment`
-                TextAlignment {
-                    horizontal: HorizontalAlign::Center,
-                    ..Default::default()
-                },
-            ),
-            ..Default::default()
-        })
-        .insert(ScoreText {});
 }
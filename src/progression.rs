@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+use crate::config::Hull;
+
+/// Where progression is kept between sessions, relative to the working directory.
+#[cfg(not(target_arch = "wasm32"))]
+const PROGRESSION_PATH: &str = "progression.ron";
+
+/// What the player has achieved across runs. Hulls unlock as the best score rises.
+/// Saved to a file on native builds; on the web it only lasts for the session.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Progression {
+    pub best_score: i32,
+    pub runs: u32,
+    pub missions_completed: u32,
+}
+
+impl Progression {
+    pub fn is_unlocked(&self, hull: &Hull) -> bool {
+        self.best_score >= hull.unlock_score
+    }
+
+    /// Count a finished run. Returns whether it set a new best score.
+    pub fn record_run(&mut self, score: i32) -> bool {
+        self.runs += 1;
+        self.record_score(score)
+    }
+
+    /// Count a score towards the best one without ending the run.
+    /// Returns whether it set a new best score.
+    pub fn record_score(&mut self, score: i32) -> bool {
+        if score > self.best_score {
+            self.best_score = score;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Read saved progression, starting afresh if there is none or it can't be read.
+    pub fn load() -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Ok(contents) = std::fs::read_to_string(PROGRESSION_PATH) {
+            match ron::de::from_str(&contents) {
+                Ok(progression) => return progression,
+                Err(err) => bevy::log::warn!("Could not read progression: {}", err),
+            }
+        }
+        Self::default()
+    }
+
+    pub fn save(&self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let result = ron::ser::to_string_pretty(self, Default::default())
+                .map_err(|err| err.to_string())
+                .and_then(|contents| {
+                    std::fs::write(PROGRESSION_PATH, contents).map_err(|err| err.to_string())
+                });
+            if let Err(err) = result {
+                bevy::log::warn!("Could not save progression: {}", err);
+            }
+        }
+    }
+}
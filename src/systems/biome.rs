@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::{
+        camera::CameraController,
+        chunk::{Chunk, WorldSeed},
+    },
+    config::GameConfig,
+    util::project2d,
+};
+
+/// Fade the background towards the tint of the biome under the camera.
+pub fn biome_background(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    seed: Res<WorldSeed>,
+    camera: Query<&Transform, With<CameraController>>,
+    mut clear_color: ResMut<ClearColor>,
+) {
+    /// Fraction of the way to the new tint covered per second.
+    const FADE_RATE: f32 = 0.5;
+
+    let transform = match camera.single() {
+        Ok(transform) => transform,
+        Err(_) => return,
+    };
+    let size = config.world.chunk_size;
+    let (x, y) = Chunk::containing_point(&project2d(transform.translation), size).grid_coords(size);
+    let (r, g, b) = config
+        .biomes
+        .sample(seed.0, x, y)
+        .map_or((0.0, 0.0, 0.0), |biome| biome.tint);
+
+    let t = (FADE_RATE * time.delta_seconds()).min(1.0);
+    let current = clear_color.0;
+    let blend = |from: f32, to: f32| from + (to - from) * t;
+    let color = Color::rgb(
+        blend(current.r(), r),
+        blend(current.g(), g),
+        blend(current.b(), b),
+    );
+    if color != current {
+        clear_color.0 = color;
+    }
+}
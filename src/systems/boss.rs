@@ -0,0 +1,224 @@
+use bevy::{prelude::*, utils::HashMap};
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+
+use crate::{
+    components::{
+        boss::{
+            Boss, BossBar, BossBarFill, BossBarText, BossEncounters, BossKind, BossPart, Fighter,
+        },
+        types::{Player, Sturdiness},
+    },
+    config::{GameConfig, Shapes},
+    entities::{boss::BossPartBundle, enemy::EnemyBundle},
+    util::from_polar,
+};
+
+/// Bring on the next boss each time the player gets another milestone further from the origin.
+pub fn spawn_bosses(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    mut encounters: ResMut<BossEncounters>,
+    player: Query<(&RigidBodyPosition, &RigidBodyVelocity), With<Player>>,
+) {
+    let (pos, vel) = match player.single() {
+        Ok(player) => player,
+        Err(_) => return,
+    };
+    let bosses = &config.bosses;
+    let position: Vec2 = pos.position.translation.into();
+    let milestone = (position.length() / bosses.milestone_distance) as u32;
+    if milestone <= encounters.0 || bosses.order.is_empty() {
+        return;
+    }
+    encounters.0 = milestone;
+
+    // Appear ahead of the player, or in a random direction if they are sitting still.
+    let velocity: Vec2 = vel.linvel.into();
+    let ahead = if velocity.length() > 1.0 {
+        velocity.normalize()
+    } else {
+        from_polar(
+            1.0,
+            rand::thread_rng().gen_range(0.0..std::f32::consts::TAU),
+        )
+    };
+    let kind = bosses.order[(milestone as usize - 1) % bosses.order.len()];
+    info!("{} approaching", kind.name());
+    BossPartBundle::spawn_boss(
+        &mut commands,
+        kind,
+        position + ahead * bosses.spawn_distance,
+        bosses,
+    );
+}
+
+/// Work out each boss's health from its weak points, and fight according to its kind and phase.
+/// A boss whose weak points are all gone breaks apart, as does anything left of one whose core
+/// was destroyed.
+pub fn boss_ai(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    shapes: Res<Shapes>,
+    player: Query<&RigidBodyPosition, With<Player>>,
+    mut bosses: Query<
+        (
+            Entity,
+            &mut Boss,
+            &mut Sturdiness,
+            &RigidBodyPosition,
+            &mut RigidBodyVelocity,
+        ),
+        Without<Player>,
+    >,
+    mut parts: Query<(&BossPart, &mut Sturdiness, &RigidBodyPosition), Without<Boss>>,
+    fighters: Query<&Fighter>,
+) {
+    let dt = time.delta_seconds();
+    let bosses_config = &config.bosses;
+    let target: Option<Vec2> = player
+        .single()
+        .ok()
+        .map(|pos| pos.position.translation.into());
+
+    let mut weak_points: HashMap<Entity, f32> = HashMap::default();
+    // Where each boss can launch fighters from, if it can.
+    let mut hangars: HashMap<Entity, Vec2> = HashMap::default();
+    for (part, sturdiness, pos) in parts.iter_mut() {
+        if part.weak_point {
+            *weak_points.entry(part.boss).or_insert(0.0) += sturdiness.0.max(0.0);
+            hangars.insert(part.boss, pos.position.translation.into());
+        }
+    }
+
+    let mut launched: HashMap<Entity, u32> = HashMap::default();
+    for fighter in fighters.iter() {
+        *launched.entry(fighter.carrier).or_insert(0) += 1;
+    }
+
+    let mut defeated = Vec::new();
+    for (entity, mut boss, mut sturdiness, pos, mut vel) in bosses.iter_mut() {
+        let left = weak_points.get(&entity).copied().unwrap_or(0.0);
+        boss.health = if boss.max_health > 0.0 {
+            (left / boss.max_health).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        if boss.health <= 0.0 {
+            info!("{} defeated", boss.kind.name());
+            sturdiness.0 = 0.0;
+            defeated.push(entity);
+            continue;
+        }
+        let phase = Boss::phase_for(boss.health);
+        if phase != boss.phase {
+            info!("{} enters phase {}", boss.kind.name(), phase);
+            boss.phase = phase;
+        }
+
+        let position: Vec2 = pos.position.translation.into();
+        let offset = target.map_or(Vec2::ZERO, |target| target - position);
+        let distance = offset.length();
+        let toward = if distance > 0.0 {
+            offset / distance
+        } else {
+            Vec2::ZERO
+        };
+        // Each phase past the first pushes the boss half as hard again.
+        let fury = 1.0 + 0.5 * (boss.phase - 1) as f32;
+        let (desired, spin) = match boss.kind {
+            BossKind::Worm => (toward * bosses_config.worm_speed * fury, None),
+            BossKind::Carrier => {
+                let standoff = bosses_config.carrier_standoff;
+                let closing = ((distance - standoff) / standoff.max(1.0)).clamp(-1.0, 1.0);
+                boss.cooldown -= dt;
+                if let (Some(_), Some(hangar)) = (target, hangars.get(&entity)) {
+                    let out = launched.get(&entity).copied().unwrap_or(0);
+                    if boss.cooldown <= 0.0 && out < bosses_config.max_fighters {
+                        boss.cooldown = bosses_config.launch_interval / fury;
+                        let launch = *hangar + toward * 40.0;
+                        match EnemyBundle::new(launch.into(), &config.enemy, &shapes) {
+                            Ok(fighter) => {
+                                commands
+                                    .spawn_bundle(fighter)
+                                    .insert(Fighter { carrier: entity });
+                            }
+                            Err(err) => warn!("Could not launch fighter: {}", err),
+                        }
+                    }
+                }
+                (toward * closing * bosses_config.carrier_speed, Some(0.0))
+            }
+            BossKind::Golem => {
+                // The last phase gives up on spinning in place and charges.
+                let speed = if boss.phase == Boss::PHASES { 3.0 } else { 1.0 };
+                (
+                    toward * bosses_config.golem_speed * speed,
+                    Some(bosses_config.golem_spin * fury),
+                )
+            }
+        };
+        // Ease into the new velocity, so the joints have time to drag the other parts along.
+        let blend = (2.0 * dt).min(1.0);
+        let current: Vec2 = vel.linvel.into();
+        vel.linvel = (current + (desired - current) * blend).into();
+        if let Some(spin) = spin {
+            vel.angvel += (spin - vel.angvel) * blend;
+        }
+    }
+
+    for (part, mut sturdiness, _) in parts.iter_mut() {
+        if defeated.contains(&part.boss) || bosses.get_mut(part.boss).is_err() {
+            sturdiness.0 = 0.0;
+        }
+    }
+}
+
+/// Show the closest boss's name, phase and health while it is within range.
+pub fn boss_bar(
+    config: Res<GameConfig>,
+    player: Query<&RigidBodyPosition, With<Player>>,
+    bosses: Query<(&Boss, &RigidBodyPosition), Without<Player>>,
+    mut nodes: Query<&mut Visible, With<BossBar>>,
+    mut fill: Query<&mut Style, With<BossBarFill>>,
+    mut text: Query<&mut Text, With<BossBarText>>,
+) {
+    let closest = player.single().ok().and_then(|pos| {
+        let position: Vec2 = pos.position.translation.into();
+        bosses
+            .iter()
+            .map(|(boss, pos)| (boss, position.distance(pos.position.translation.into())))
+            .filter(|(_, distance)| *distance <= config.bosses.engage_range)
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .map(|(boss, _)| boss)
+    });
+
+    let shown = closest.is_some();
+    for mut visible in nodes.iter_mut() {
+        if visible.is_visible != shown {
+            visible.is_visible = shown;
+        }
+    }
+    let boss = match closest {
+        Some(boss) => boss,
+        None => return,
+    };
+    if let Ok(mut style) = fill.single_mut() {
+        let width = Val::Percent(boss.health * 100.0);
+        if style.size.width != width {
+            style.size.width = width;
+        }
+    }
+    if let Ok(mut text) = text.single_mut() {
+        let label = format!(
+            "{} (phase {}/{})",
+            boss.kind.name(),
+            boss.phase,
+            Boss::PHASES
+        );
+        if text.sections[0].value != label {
+            text.sections[0].value = label;
+        }
+    }
+}
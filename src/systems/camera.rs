@@ -0,0 +1,110 @@
+use bevy::{prelude::*, render::camera::OrthographicProjection};
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+
+use crate::{
+    components::{
+        camera::CameraController,
+        damage::Category,
+        events::{DestroyedEvent, ImpactEvent},
+        types::Player,
+    },
+    config::GameConfig,
+    util::smooth_damp,
+};
+
+/// Shake from events this far from the camera is halved.
+const SHAKE_FALLOFF: f32 = 600.0;
+
+/// Shake the camera on impacts and explosions, less the further away they are.
+pub fn camera_shake(
+    config: Res<GameConfig>,
+    mut impacts: EventReader<ImpactEvent>,
+    mut destroyed: EventReader<DestroyedEvent>,
+    mut cameras: Query<&mut CameraController>,
+) {
+    let impacts = impacts
+        .iter()
+        .map(|impact| (impact.position, impact.energy / config.camera.shake_energy));
+    let explosions = destroyed.iter().filter_map(|event| {
+        let trauma = match event.category {
+            Category::Ship | Category::Enemy => 0.6,
+            Category::Asteroid => 0.25,
+            _ => return None,
+        };
+        Some((event.position, trauma))
+    });
+    let shakes = impacts.chain(explosions).collect::<Vec<_>>();
+
+    for mut camera in cameras.iter_mut() {
+        for (position, trauma) in shakes.iter() {
+            let falloff = 1.0 / (1.0 + camera.position.distance(*position) / SHAKE_FALLOFF);
+            camera.trauma = (camera.trauma + trauma * falloff).min(1.0);
+        }
+    }
+}
+
+/// Follow the player, looking ahead along their velocity and zooming out as they speed up.
+pub fn camera_controller(
+    config: Res<GameConfig>,
+    time: Res<Time>,
+    player: Query<(&RigidBodyPosition, &RigidBodyVelocity), With<Player>>,
+    mut cameras: Query<(
+        &mut CameraController,
+        &mut Transform,
+        &mut OrthographicProjection,
+    )>,
+) {
+    let config = &config.camera;
+    let dt = time.delta_seconds();
+    let mut rng = rand::thread_rng();
+    let target = player.single().ok().map(|(pos, vel)| {
+        let position: Vec2 = pos.position.translation.into();
+        let velocity: Vec2 = vel.linvel.into();
+        (position, velocity)
+    });
+
+    for (mut camera, mut transform, mut projection) in cameras.iter_mut() {
+        if let Some((position, velocity)) = target {
+            let look_ahead = (velocity * config.look_ahead).clamp_length_max(config.max_look_ahead);
+            let goal = position + look_ahead;
+            let mut camera_velocity = camera.velocity;
+            let x = smooth_damp(
+                camera.position.x,
+                goal.x,
+                &mut camera_velocity.x,
+                config.smoothing,
+                dt,
+            );
+            let y = smooth_damp(
+                camera.position.y,
+                goal.y,
+                &mut camera_velocity.y,
+                config.smoothing,
+                dt,
+            );
+            camera.position = Vec2::new(x, y);
+            camera.velocity = camera_velocity;
+
+            let speed = (velocity.length() / config.zoom_speed).min(1.0);
+            let goal_zoom = config.min_zoom + (config.max_zoom - config.min_zoom) * speed;
+            let mut zoom_velocity = camera.zoom_velocity;
+            camera.zoom = smooth_damp(
+                camera.zoom,
+                goal_zoom,
+                &mut zoom_velocity,
+                config.zoom_smoothing,
+                dt,
+            );
+            camera.zoom_velocity = zoom_velocity;
+        }
+
+        let shake = config.max_shake * camera.trauma * camera.trauma;
+        let offset = Vec2::new(rng.gen_range(-1.0..=1.0), rng.gen_range(-1.0..=1.0)) * shake;
+        camera.trauma = (camera.trauma - config.shake_decay * dt).max(0.0);
+
+        let position = camera.position + offset * camera.zoom;
+        transform.translation = position.extend(transform.translation.z);
+        projection.scale = camera.zoom;
+    }
+}
@@ -1,109 +1,215 @@
-use bevy::{ecs::query::QueryEntityError, prelude::*};
+use bevy::{
+    ecs::query::{FilterFetch, QueryEntityError, WorldQuery},
+    prelude::*,
+};
 use bevy_rapier2d::prelude::*;
 // use rand::Rng;
 
 use crate::{
     components::{
-        chunk::{self, SpawnedChunks},
+        boss::{Boss, BossPart},
+        chunk::{self, SpawnedChunks, WorldSeed},
+        console::{parse_arg, CommandResult, GodMode},
+        damage::*,
+        difficulty::Difficulty,
+        events::{DestroyedEvent, ImpactEvent},
+        gravity::GravityWell,
+        material::{Material, MaterialProps},
+        mission::CargoDrone,
+        pickup::{Pickup, PickupKind},
+        shield::Shield,
+        station::Station,
         types::*,
     },
-    entities::asteroid::AsteroidBundle,
+    config::{GameConfig, Shapes},
+    entities::{asteroid::AsteroidBundle, enemy::EnemyBundle, pickup::PickupBundle},
+    systems::console::cursor_world_position,
     util::project2d,
 };
 
+/// Snapshot of one side of a collision.
+struct CollisionBody {
+    entity: Entity,
+    mass: f32,
+    velocity: Vec2,
+    position: Vec2,
+    category: Category,
+    material: MaterialProps,
+    owner: Option<Owner>,
+}
+
+impl CollisionBody {
+    fn get(
+        bodies: &mut Query<(
+            &mut Sturdiness,
+            &RigidBodyMassProps,
+            &RigidBodyVelocity,
+            &RigidBodyPosition,
+            &Category,
+            &Material,
+            Option<&Owner>,
+            Option<&mut LastImpact>,
+            Option<&mut Shield>,
+        )>,
+        entity: Entity,
+    ) -> Result<Self, QueryEntityError> {
+        let obj = bodies.get_mut(entity)?;
+        Ok(Self {
+            entity,
+            mass: obj.1.local_mprops.inv_mass.recip(),
+            velocity: obj.2.linvel.into(),
+            position: obj.3.position.translation.into(),
+            category: *obj.4,
+            material: obj.5.props(),
+            owner: obj.6.cloned(),
+        })
+    }
+}
+
+/// Where two colliders touch in world space, going by the contact points the solver used for them.
+fn contact_point(narrow_phase: &NarrowPhase, a: ColliderHandle, b: ColliderHandle) -> Option<Vec2> {
+    let pair = narrow_phase.contact_pair(a, b)?;
+    let contacts = pair
+        .manifolds
+        .iter()
+        .flat_map(|manifold| manifold.data.solver_contacts.iter())
+        .collect::<Vec<_>>();
+    if contacts.is_empty() {
+        return None;
+    }
+    let sum = contacts
+        .iter()
+        .fold(Vec2::ZERO, |sum, contact| sum + Vec2::from(contact.point));
+    Some(sum / contacts.len() as f32)
+}
+
 fn process_collision(
+    commands: &mut Commands,
+    rules: &DamageRules,
     bodies: &mut Query<(
-        Entity,
         &mut Sturdiness,
         &RigidBodyMassProps,
         &RigidBodyVelocity,
+        &RigidBodyPosition,
+        &Category,
+        &Material,
+        Option<&Owner>,
+        Option<&mut LastImpact>,
+        Option<&mut Shield>,
     )>,
-    a: Entity,
-    b: Entity,
-) -> Result<(), QueryEntityError> {
-    let mut get_components = |e: Entity| -> Result<(f32, Vec2), QueryEntityError> {
-        let obj = bodies.get_mut(e)?;
-        let m = obj.2.local_mprops.inv_mass.recip();
-        let v: Vec2 = obj.3.linvel.into();
-        Ok((m, v))
-    };
-    let restitution: f32 = 0.9;
-    let k = 500.0_f32.recip();
-
-    let (ma, va) = get_components(a)?;
-    let (mb, vb) = get_components(b)?;
-    let relv2 = (va - vb).length_squared();
+    piercing: &mut Query<&mut Piercing>,
+    narrow_phase: &NarrowPhase,
+    protected: Option<Entity>,
+    colliders: (ColliderHandle, ColliderHandle),
+) -> Result<ImpactEvent, QueryEntityError> {
+    let mut a = CollisionBody::get(bodies, colliders.0.entity())?;
+    let mut b = CollisionBody::get(bodies, colliders.1.entity())?;
+    // Static bodies have infinite mass. Hitting one is like hitting a copy of yourself coming
+    // the other way, so give it the other body's mass to keep the energies finite.
+    if !a.mass.is_finite() {
+        a.mass = b.mass;
+    } else if !b.mass.is_finite() {
+        b.mass = a.mass;
+    }
+    let relv2 = (a.velocity - b.velocity).length_squared();
+    // Rapier averages the restitution of both colliders by default, so do the same here.
+    let restitution = 0.5 * (a.material.restitution + b.material.restitution);
+    let absorbed = 1.0 - restitution.powf(2.0);
 
-    let e_a = 0.5 * mb * relv2;
-    let e_b = 0.5 * ma * relv2;
+    let e_a = 0.5 * b.mass * relv2 * rules.multiplier(b.category, a.category);
+    let e_b = 0.5 * a.mass * relv2 * rules.multiplier(a.category, b.category);
 
-    let rel_e_a = e_a * (1.0 - restitution.powf(2.0));
-    let rel_e_b = e_b * (1.0 - restitution.powf(2.0));
+    let rel_e_a = e_a * absorbed;
+    let rel_e_b = e_b * absorbed;
 
-    let mut step_sturdiness = |entity, energy: f32| -> Result<(), QueryEntityError> {
-        let mut sturdiness = bodies.get_mut(entity)?.1;
-        sturdiness.0 = sturdiness.0 - k * energy / sturdiness.0;
+    let mut step_sturdiness = |entity, energy: f32, k: f32| -> Result<(), QueryEntityError> {
+        let (mut sturdiness, .., shield) = bodies.get_mut(entity)?;
+        let mut damage = k * energy / sturdiness.0;
+        // Shields take the hit first, and only pass on what they can't absorb.
+        if let Some(mut shield) = shield {
+            if damage > 0.0 {
+                damage = shield.absorb(damage);
+            }
+        }
+        sturdiness.0 -= damage;
         Ok(())
     };
-    let _ = step_sturdiness(a, rel_e_a)?;
-    let _ = step_sturdiness(b, rel_e_b)?;
+    if protected != Some(a.entity) && rules.allows(b.owner.as_ref(), a.entity, a.category) {
+        step_sturdiness(a.entity, rel_e_a, a.material.damage_coefficient)?;
+    }
+    if protected != Some(b.entity) && rules.allows(a.owner.as_ref(), b.entity, b.category) {
+        step_sturdiness(b.entity, rel_e_b, b.material.damage_coefficient)?;
+    }
+
+    // Bullets are spent on impact, unless they can still pierce through.
+    for body in [&a, &b] {
+        if body.category != Category::Bullet {
+            continue;
+        }
+        match piercing.get_mut(body.entity) {
+            Ok(mut hits) if hits.0 > 0 => hits.0 -= 1,
+            _ => commands.entity(body.entity).despawn(),
+        }
+    }
 
-    Ok(())
+    // Without contact points to go on, weighting each position by the other body's mass puts the
+    // contact near the lighter body, which is usually the one doing the hitting.
+    let total_mass = a.mass + b.mass;
+    let impact = Impact {
+        position: contact_point(narrow_phase, colliders.0, colliders.1)
+            .unwrap_or_else(|| (a.position * b.mass + b.position * a.mass) / total_mass),
+        energy: 0.5 * total_mass * relv2 * absorbed,
+    };
+    for entity in [a.entity, b.entity] {
+        if let (.., Some(mut last), _) = bodies.get_mut(entity)? {
+            last.0 = Some(impact);
+        }
+    }
+    Ok(ImpactEvent {
+        position: impact.position,
+        energy: impact.energy,
+    })
 }
 
 pub fn damage(
+    mut commands: Commands,
     mut contact_events: EventReader<ContactEvent>,
+    mut impacts: EventWriter<ImpactEvent>,
+    rules: Res<DamageRules>,
+    narrow_phase: Res<NarrowPhase>,
+    god_mode: Res<GodMode>,
+    player: Query<Entity, With<Player>>,
     mut bodies: Query<(
-        Entity,
         &mut Sturdiness,
         &RigidBodyMassProps,
         &RigidBodyVelocity,
+        &RigidBodyPosition,
+        &Category,
+        &Material,
+        Option<&Owner>,
+        Option<&mut LastImpact>,
+        Option<&mut Shield>,
     )>,
+    mut piercing: Query<&mut Piercing>,
 ) {
-    for contact_event in contact_events.iter() {
-        match contact_event {
-            ContactEvent::Started(a, b) => {
-                let _ = process_collision(&mut bodies, a.entity(), b.entity());
-            }
-            _ => (),
-        }
-    }
-}
-
-pub fn damage_old(
-    mut commands: Commands,
-    mut contact_events: EventReader<ContactEvent>,
-    mut bodies: Query<(Entity, &mut Sturdiness), Without<Bullet>>,
-    bullets: Query<Entity, With<Bullet>>,
-    mut score: ResMut<Score>,
-) {
-    let is_bullet = |x: &ColliderHandle| bullets.get(x.entity()).is_ok();
-    let sort_collision = |a, b: &ColliderHandle| {
-        if is_bullet(a) {
-            Some((b.entity(), a.entity()))
-        } else if is_bullet(b) {
-            Some((a.entity(), b.entity()))
-        } else {
-            None
-        }
+    let protected = if god_mode.0 {
+        player.single().ok()
+    } else {
+        None
     };
     for contact_event in contact_events.iter() {
-        match contact_event {
-            ContactEvent::Started(a, b) => {
-                if let Some((target, bullet)) = sort_collision(a, b) {
-                    commands.entity(bullet).despawn();
-                    if let Ok((_entity, mut health)) = bodies.get_mut(target) {
-                        health.0 -= 100.0;
-                        score.0 += 1;
-                        println!("Health for target: {}", health.0);
-                    }
-                }
-                let ea = a.entity();
-                let eb = b.entity();
-                commands.entity(ea).despawn_recursive();
-                commands.entity(eb).despawn_recursive();
+        if let ContactEvent::Started(a, b) = contact_event {
+            if let Ok(impact) = process_collision(
+                &mut commands,
+                &rules,
+                &mut bodies,
+                &mut piercing,
+                &narrow_phase,
+                protected,
+                (*a, *b),
+            ) {
+                impacts.send(impact);
             }
-            _ => (),
         }
     }
 }
@@ -120,29 +226,93 @@ pub fn despawn(
     }
 }
 
-pub fn health(mut commands: Commands, mut query: Query<(Entity, &Sturdiness)>) {
-    for (ent, sturdiness) in query.iter_mut() {
+pub fn health(
+    mut commands: Commands,
+    mut destroyed: EventWriter<DestroyedEvent>,
+    mut query: Query<(
+        Entity,
+        &Sturdiness,
+        &Category,
+        &Transform,
+        Option<&RigidBodyPosition>,
+        Option<&RigidBodyVelocity>,
+        Option<&RigidBodyMassProps>,
+        Option<&Material>,
+        Option<&Outline>,
+        Option<&LastImpact>,
+        Option<&Fragment>,
+    )>,
+) {
+    for (
+        ent,
+        sturdiness,
+        category,
+        transform,
+        pos,
+        velocity,
+        mass,
+        material,
+        outline,
+        impact,
+        fragment,
+    ) in query.iter_mut()
+    {
         if sturdiness.0 <= 0.0 {
-            commands.entity(ent).despawn()
+            commands.entity(ent).despawn_recursive();
+            destroyed.send(DestroyedEvent {
+                entity: ent,
+                category: *category,
+                position: project2d(transform.translation),
+                rotation: pos.map_or(0.0, |p| p.position.rotation.angle()),
+                velocity: velocity.map_or(Vec2::ZERO, |v| v.linvel.into()),
+                angular_velocity: velocity.map_or(0.0, |v| v.angvel),
+                mass: mass.map_or(0.0, |m| m.local_mprops.inv_mass.recip()),
+                material: material.copied(),
+                // Only asteroids break up along their outline, so don't copy anyone else's.
+                outline: match (category, outline) {
+                    (Category::Asteroid, Some(outline)) => outline.0.clone(),
+                    _ => Vec::new(),
+                },
+                impact: impact.and_then(|impact| impact.0),
+                fragment: fragment.is_some(),
+            });
         }
     }
 }
 
 pub fn spawn_asteroids(
     mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    difficulty: Res<Difficulty>,
+    shapes: Res<Shapes>,
+    seed: Res<WorldSeed>,
     player: Query<&Transform, With<Player>>,
     mut spawned_chunks: ResMut<SpawnedChunks>,
 ) {
     if let Ok(player_pos) = player.single() {
+        let chunk_size = config.world.chunk_size;
         let surrounding_chunks: Vec<chunk::Chunk> =
-            chunk::Chunk::containing_point(&project2d(player_pos.translation)).surrounding_chunks();
+            chunk::Chunk::containing_point(&project2d(player_pos.translation), chunk_size)
+                .surrounding_chunks(chunk_size);
         let chunks_to_spawn = surrounding_chunks
             .into_iter()
             .filter(|c| !spawned_chunks.0.contains(c))
             .collect::<Vec<_>>();
 
+        let elapsed = (time.seconds_since_startup() - difficulty.run_started) as f32;
         for chunk in chunks_to_spawn {
-            AsteroidBundle::spawn_for_chunk(&mut commands, &chunk);
+            let center = chunk.bottom_left() + Vec2::splat(chunk_size / 2.0);
+            let level = config.difficulty.level(center.length(), elapsed);
+            let scaling = config.difficulty.scaling(difficulty.preset, level);
+            AsteroidBundle::spawn_for_chunk(
+                &mut commands,
+                &chunk,
+                &config,
+                &shapes,
+                seed.0,
+                &scaling,
+            );
             spawned_chunks.0.insert(chunk);
         }
 
@@ -170,3 +340,102 @@ pub fn spawn_asteroids(
         // }
     }
 }
+
+pub fn spawn_command(world: &mut World, args: &[&str]) -> CommandResult {
+    let pos = cursor_world_position(world).ok_or("cursor is outside the window")?;
+    let config = world.get_resource::<GameConfig>().unwrap().clone();
+    let shapes = world.get_resource::<Shapes>().unwrap().clone();
+    match args.first() {
+        Some(&"asteroid") => {
+            let asteroid = AsteroidBundle::new(
+                pos.into(),
+                Default::default(),
+                Material::Rock,
+                &config.asteroid,
+                &shapes.asteroid,
+                1.0,
+            )
+            .map_err(|err| err.to_string())?;
+            world.spawn().insert_bundle(asteroid);
+        }
+        Some(&"enemy") => {
+            let enemy = EnemyBundle::new(pos.into(), &config.enemy, &shapes)
+                .map_err(|err| err.to_string())?;
+            world.spawn().insert_bundle(enemy);
+        }
+        Some(&"pickup") => {
+            const USAGE: &str =
+                "usage: spawn pickup <fuel|repair|ammo|credits|double_score|overdrive|iron|ice|crystal> [amount]";
+            let name = args.get(1).ok_or(USAGE)?;
+            let kind = PickupKind::NAMED
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, kind)| *kind)
+                .ok_or(USAGE)?;
+            let amount = if args.len() > 2 {
+                parse_arg(args, 2, "amount")?
+            } else {
+                10.0
+            };
+            let now = world
+                .get_resource::<Time>()
+                .unwrap()
+                .seconds_since_startup();
+            world.spawn().insert_bundle(PickupBundle::new(
+                pos,
+                Vec2::ZERO,
+                kind,
+                amount,
+                now,
+                config.loot.lifetime,
+            ));
+        }
+        _ => return Err("usage: spawn <asteroid|enemy|pickup>".to_string()),
+    }
+    Ok(format!(
+        "spawned {} at ({:.0}, {:.0})",
+        args[0], pos.x, pos.y
+    ))
+}
+
+/// Despawn everything matching `F`, and forget which chunks were generated so they fill in again
+/// around the player.
+fn regenerate_chunks<F: WorldQuery>(world: &mut World)
+where
+    F::Fetch: FilterFetch,
+{
+    let generated = world
+        .query_filtered::<Entity, F>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    for entity in generated {
+        despawn_with_children_recursive(world, entity);
+    }
+    world.get_resource_mut::<SpawnedChunks>().unwrap().0.clear();
+}
+
+/// Change the world seed, and clear out everything generated with chunks so they regenerate around the player.
+pub fn seed_command(world: &mut World, args: &[&str]) -> CommandResult {
+    let seed: u64 = parse_arg(args, 0, "seed")?;
+    world.insert_resource(WorldSeed(seed));
+    regenerate_chunks::<Or<(With<Asteroid>, With<GravityWell>, With<Station>)>>(world);
+    Ok(format!("regenerating with seed {}", seed))
+}
+
+/// Everything a run leaves lying around in the world, besides the player.
+type LeftOver = Or<(
+    With<Asteroid>,
+    With<GravityWell>,
+    With<Station>,
+    With<Enemy>,
+    With<Bullet>,
+    With<Pickup>,
+    With<Boss>,
+    With<BossPart>,
+    With<CargoDrone>,
+)>;
+
+/// Start each run in a fresh world, rather than among what the last one left behind.
+pub fn reset_world(world: &mut World) {
+    regenerate_chunks::<LeftOver>(world);
+}
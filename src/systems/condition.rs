@@ -0,0 +1,168 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+
+use crate::{
+    components::{
+        damage::{Condition, Cracks, HullWarningText},
+        particle::{ParticleBurst, ParticleKind},
+        types::{Asteroid, MaxSturdiness, Outline, Player, Sturdiness},
+    },
+    entities::{
+        asteroid::{CracksBundle, MAX_CRACKS},
+        entity::build_outlines_geometry,
+    },
+};
+
+/// Draw more cracks over asteroids as they lose sturdiness.
+pub fn asteroid_cracks(
+    mut commands: Commands,
+    asteroids: Query<
+        (
+            Entity,
+            &Sturdiness,
+            &MaxSturdiness,
+            &Outline,
+            Option<&Children>,
+        ),
+        (With<Asteroid>, Changed<Sturdiness>),
+    >,
+    cracks: Query<&Cracks>,
+) {
+    for (entity, sturdiness, max, outline, children) in asteroids.iter() {
+        let damage = (1.0 - sturdiness.0 / max.0).clamp(0.0, 1.0);
+        let count = (damage * (MAX_CRACKS + 1) as f32) as u32;
+        let count = count.min(MAX_CRACKS);
+
+        let existing = children.and_then(|children| {
+            children
+                .iter()
+                .find_map(|child| cracks.get(*child).ok().map(|c| (*child, c.count)))
+        });
+        if existing.map_or(0, |(_, drawn)| drawn) >= count {
+            continue;
+        }
+        let outline = match outline.0.first() {
+            Some(outline) => outline
+                .iter()
+                .map(|(x, y)| Vec2::new(*x, *y))
+                .collect::<Vec<_>>(),
+            None => continue,
+        };
+        if let Some((child, _)) = existing {
+            commands.entity(child).despawn();
+        }
+        let crack = commands
+            .spawn_bundle(CracksBundle::new(&outline, count, entity.id() as u64))
+            .id();
+        commands.entity(entity).push_children(&[crack]);
+    }
+}
+
+/// Recolor ships as their hull weakens.
+pub fn ship_condition(
+    mut commands: Commands,
+    mut ships: Query<
+        (
+            Entity,
+            &Sturdiness,
+            &MaxSturdiness,
+            &mut Condition,
+            &Outline,
+            &Transform,
+        ),
+        Changed<Sturdiness>,
+    >,
+) {
+    for (entity, sturdiness, max, mut condition, outline, transform) in ships.iter_mut() {
+        let current = Condition::from_fraction(sturdiness.0 / max.0);
+        if current == *condition {
+            continue;
+        }
+        *condition = current;
+        // Rebuilding the shape replaces the transform, so carry the current one over.
+        let mut geometry = build_outlines_geometry(&outline.0, current.color());
+        geometry.transform = *transform;
+        commands.entity(entity).insert_bundle(geometry);
+    }
+}
+
+/// Make ships in critical condition flicker and trail sparks.
+pub fn critical_ship_effects(
+    time: Res<Time>,
+    mut bursts: EventWriter<ParticleBurst>,
+    mut ships: Query<(
+        &Condition,
+        &mut Visible,
+        &RigidBodyPosition,
+        &RigidBodyVelocity,
+    )>,
+) {
+    const FLICKER_HZ: f64 = 8.0;
+    const SPARKS_PER_SECOND: f64 = 6.0;
+
+    let mut rng = rand::thread_rng();
+    let flicker_on = (time.seconds_since_startup() * FLICKER_HZ).fract() > 0.3;
+    let spark_chance = (SPARKS_PER_SECOND * time.delta_seconds_f64()).min(1.0);
+    for (condition, mut visible, pos, vel) in ships.iter_mut() {
+        let critical = *condition == Condition::Critical;
+        let shown = !critical || flicker_on;
+        if visible.is_visible != shown {
+            visible.is_visible = shown;
+        }
+        if critical && rng.gen_bool(spark_chance) {
+            bursts.send(ParticleBurst {
+                kind: ParticleKind::Spark,
+                position: pos.position.translation.into(),
+                velocity: vel.linvel.into(),
+                count: 2,
+                speed: 60.0,
+            });
+        }
+    }
+}
+
+pub fn spawn_hull_warning(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn_bundle(TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(5.0),
+                    left: Val::Percent(45.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            text: Text::with_section(
+                "HULL CRITICAL",
+                TextStyle {
+                    font_size: 24.0,
+                    color: Color::ORANGE_RED,
+                    font: asset_server.load("FiraSans-Bold.ttf"),
+                },
+                Default::default(),
+            ),
+            visible: Visible {
+                is_visible: false,
+                is_transparent: true,
+            },
+            ..Default::default()
+        })
+        .insert(HullWarningText {});
+}
+
+/// Flash the low-hull warning while the player's ship is in critical condition.
+pub fn hull_warning(
+    time: Res<Time>,
+    player: Query<&Condition, With<Player>>,
+    mut text: Query<&mut Visible, With<HullWarningText>>,
+) {
+    let critical = matches!(player.single(), Ok(Condition::Critical));
+    let shown = critical && (time.seconds_since_startup() * 2.0).fract() < 0.6;
+    for mut visible in text.iter_mut() {
+        if visible.is_visible != shown {
+            visible.is_visible = shown;
+        }
+    }
+}
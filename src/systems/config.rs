@@ -0,0 +1,157 @@
+use bevy::{asset::LoadState, prelude::*};
+
+use crate::{
+    components::{
+        ship::Engines,
+        types::{AppState, Player, SelectedHull},
+    },
+    config::{
+        ConfigHandle, GameConfig, HullCatalogue, HullsHandle, Shapes, ShapesHandle, CONFIG_PATH,
+        HULLS_PATH, SHAPES_PATH,
+    },
+};
+
+pub fn load_config(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(ConfigHandle(asset_server.load(CONFIG_PATH)));
+    commands.insert_resource(ShapesHandle(asset_server.load(SHAPES_PATH)));
+    commands.insert_resource(HullsHandle(asset_server.load(HULLS_PATH)));
+
+    #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+    if let Err(err) = asset_server.watch_for_changes() {
+        warn!("Could not watch assets for changes: {:?}", err);
+    }
+}
+
+/// Hold the game in `AppState::Loading` until the config, shapes and hulls have loaded or failed to load.
+/// Anything that fails to load keeps its defaults.
+pub fn wait_for_config(
+    asset_server: Res<AssetServer>,
+    config_handle: Res<ConfigHandle>,
+    shapes_handle: Res<ShapesHandle>,
+    hulls_handle: Res<HullsHandle>,
+    configs: Res<Assets<GameConfig>>,
+    all_shapes: Res<Assets<Shapes>>,
+    catalogues: Res<Assets<HullCatalogue>>,
+    mut config: ResMut<GameConfig>,
+    mut shapes: ResMut<Shapes>,
+    mut hulls: ResMut<HullCatalogue>,
+    mut state: ResMut<State<AppState>>,
+) {
+    match asset_server.get_load_state(&config_handle.0) {
+        LoadState::Loaded => match configs.get(&config_handle.0) {
+            Some(loaded) => *config = loaded.clone(),
+            None => return,
+        },
+        LoadState::Failed => warn!("Could not load game config, using defaults"),
+        _ => return,
+    }
+    match asset_server.get_load_state(&shapes_handle.0) {
+        LoadState::Loaded => match all_shapes.get(&shapes_handle.0) {
+            Some(loaded) => *shapes = loaded.clone(),
+            None => return,
+        },
+        LoadState::Failed => warn!("Could not load shapes, using defaults"),
+        _ => return,
+    }
+    match asset_server.get_load_state(&hulls_handle.0) {
+        LoadState::Loaded => match catalogues.get(&hulls_handle.0) {
+            Some(loaded) => *hulls = loaded.clone(),
+            None => return,
+        },
+        LoadState::Failed => warn!("Could not load hulls, using defaults"),
+        _ => return,
+    }
+    let _ = state.set(AppState::HullSelect);
+}
+
+/// Copy reloaded config, shapes and hulls into their resources.
+/// Systems that read the resources every frame or on spawn pick up the changes on their own;
+/// engines on the running ship are updated here.
+pub fn apply_config_changes(
+    mut config_events: EventReader<AssetEvent<GameConfig>>,
+    mut shapes_events: EventReader<AssetEvent<Shapes>>,
+    mut hulls_events: EventReader<AssetEvent<HullCatalogue>>,
+    configs: Res<Assets<GameConfig>>,
+    all_shapes: Res<Assets<Shapes>>,
+    catalogues: Res<Assets<HullCatalogue>>,
+    selected: Res<SelectedHull>,
+    mut config: ResMut<GameConfig>,
+    mut shapes: ResMut<Shapes>,
+    mut hulls: ResMut<HullCatalogue>,
+    mut engines: Query<&mut Engines, With<Player>>,
+) {
+    for event in config_events.iter() {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                if let Some(loaded) = configs.get(handle) {
+                    info!("Applying game config changes");
+                    *config = loaded.clone();
+                }
+            }
+            _ => (),
+        }
+    }
+    for event in hulls_events.iter() {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                if let Some(loaded) = catalogues.get(handle) {
+                    info!("Applying hull changes");
+                    *hulls = loaded.clone();
+                    if let Some(hull) = hulls.hulls.get(selected.0) {
+                        for mut engines in engines.iter_mut() {
+                            engines.thrust = hull.ship.thrust;
+                            engines.spin = hull.ship.spin;
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    for event in shapes_events.iter() {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                if let Some(loaded) = all_shapes.get(handle) {
+                    info!("Applying shape changes to future spawns");
+                    *shapes = loaded.clone();
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// There is no file watcher on the web, so reload the config, shapes and hulls on a key press instead.
+/// Dropping the only strong handles lets the asset server unload the assets,
+/// after which loading them again fetches fresh copies.
+#[cfg(all(debug_assertions, target_arch = "wasm32"))]
+pub fn manual_reload(
+    keyboard_input: Res<Input<KeyCode>>,
+    asset_server: Res<AssetServer>,
+    mut config_handle: ResMut<ConfigHandle>,
+    mut shapes_handle: ResMut<ShapesHandle>,
+    mut hulls_handle: ResMut<HullsHandle>,
+    mut reloading: Local<bool>,
+) {
+    if !*reloading {
+        if keyboard_input.just_pressed(KeyCode::F9) {
+            config_handle.0 = config_handle.0.clone_weak();
+            shapes_handle.0 = shapes_handle.0.clone_weak();
+            hulls_handle.0 = hulls_handle.0.clone_weak();
+            *reloading = true;
+        }
+        return;
+    }
+
+    let unloaded = |state: LoadState| !matches!(state, LoadState::Loading | LoadState::Loaded);
+    if unloaded(asset_server.get_load_state(&config_handle.0))
+        && unloaded(asset_server.get_load_state(&shapes_handle.0))
+        && unloaded(asset_server.get_load_state(&hulls_handle.0))
+    {
+        info!("Reloading game config, shapes and hulls");
+        config_handle.0 = asset_server.load(CONFIG_PATH);
+        shapes_handle.0 = asset_server.load(SHAPES_PATH);
+        hulls_handle.0 = asset_server.load(HULLS_PATH);
+        *reloading = false;
+    }
+}
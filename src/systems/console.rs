@@ -0,0 +1,207 @@
+use bevy::{
+    prelude::*,
+    render::{
+        camera::{Camera, OrthographicProjection},
+        render_graph::base::camera,
+    },
+};
+use bevy_rapier2d::{physics::TimestepMode, prelude::*};
+
+use crate::{components::console::*, util::project2d};
+
+const HISTORY_LINES: usize = 10;
+
+pub fn spawn_console_text(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn_bundle(TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    bottom: Val::Px(5.0),
+                    left: Val::Px(15.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            text: Text::with_section(
+                "",
+                TextStyle {
+                    font_size: 18.0,
+                    color: Color::WHITE,
+                    font: asset_server.load("FiraSans-Bold.ttf"),
+                },
+                Default::default(),
+            ),
+            visible: Visible {
+                is_visible: false,
+                is_transparent: true,
+            },
+            ..Default::default()
+        })
+        .insert(ConsoleText {});
+}
+
+pub fn console_input(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut characters: EventReader<ReceivedCharacter>,
+    mut console: ResMut<Console>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Grave) {
+        console.open = !console.open;
+    }
+    if !console.open {
+        // Drop anything typed while the console was closed.
+        characters.iter().for_each(drop);
+        return;
+    }
+
+    for event in characters.iter() {
+        if !event.char.is_control() && event.char != '`' {
+            console.input.push(event.char);
+        }
+    }
+    if keyboard_input.just_pressed(KeyCode::Back) {
+        console.input.pop();
+    }
+    if keyboard_input.just_pressed(KeyCode::Return) {
+        let line = std::mem::take(&mut console.input);
+        console.submitted.push(line);
+    }
+}
+
+pub fn console_text(
+    console: Res<Console>,
+    mut text: Query<(&mut Text, &mut Visible), With<ConsoleText>>,
+) {
+    if !console.is_changed() {
+        return;
+    }
+    if let Ok((mut text, mut visible)) = text.single_mut() {
+        visible.is_visible = console.open;
+        let first = console.history.len().saturating_sub(HISTORY_LINES);
+        let mut lines = console.history[first..].to_vec();
+        lines.push(format!("> {}_", console.input));
+        text.sections[0].value = lines.join("\n");
+    }
+}
+
+/// Run submitted lines against the command registry.
+/// This needs exclusive world access, since commands can touch any part of the game.
+pub fn run_console_commands(world: &mut World) {
+    if world
+        .get_resource::<Console>()
+        .is_none_or(|console| console.submitted.is_empty())
+    {
+        return;
+    }
+    let lines = std::mem::take(&mut world.get_resource_mut::<Console>().unwrap().submitted);
+
+    for line in lines {
+        let result = world
+            .resource_scope(|world, registry: Mut<CommandRegistry>| registry.run(world, &line));
+        let mut console = world.get_resource_mut::<Console>().unwrap();
+        console.history.push(format!("> {}", line));
+        match result {
+            Ok(output) => console.history.extend(output.lines().map(String::from)),
+            Err(err) => console.history.push(format!("error: {}", err)),
+        }
+    }
+}
+
+/// Step the physics by the frame's scaled duration.
+/// Rapier's variable timestep never steps by more than `IntegrationParameters::dt`, which would
+/// cap the scale at 1, so the step is picked here and rapier takes it as a fixed one.
+pub fn apply_time_scale(
+    time: Res<Time>,
+    scale: Res<TimeScale>,
+    mut rapier: ResMut<RapierConfiguration>,
+    mut params: ResMut<IntegrationParameters>,
+) {
+    if rapier.timestep_mode != TimestepMode::FixedTimestep {
+        rapier.timestep_mode = TimestepMode::FixedTimestep;
+    }
+    params.dt = scale.physics_step(time.delta_seconds());
+}
+
+/// Where the mouse cursor is in world coordinates, for commands that act "at the cursor".
+pub fn cursor_world_position(world: &mut World) -> Option<Vec2> {
+    let (cursor, size) = {
+        let window = world.get_resource::<Windows>()?.get_primary()?;
+        (
+            window.cursor_position()?,
+            Vec2::new(window.width(), window.height()),
+        )
+    };
+    let mut cameras = world.query::<(&Transform, &Camera, &OrthographicProjection)>();
+    cameras
+        .iter(world)
+        .find(|(_, cam, _)| cam.name == Some(camera::CAMERA_2D.to_string()))
+        .map(|(transform, _, projection)| {
+            project2d(transform.translation) + (cursor - size / 2.0) * projection.scale
+        })
+}
+
+pub fn time_scale_command(world: &mut World, args: &[&str]) -> CommandResult {
+    let scale: f32 = parse_arg(args, 0, "scale")?;
+    if scale <= 0.0 {
+        return Err("time scale must be greater than zero".to_string());
+    }
+    world.insert_resource(TimeScale(scale));
+    Ok(format!("time scale set to {}", scale))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::*;
+
+    fn step_with_scale(scale: f32) -> (f32, f32) {
+        let mut world = World::default();
+        let mut time = Time::default();
+        time.update();
+        thread::sleep(Duration::from_millis(5));
+        time.update();
+        let delta = time.delta_seconds();
+        world.insert_resource(time);
+        world.insert_resource(TimeScale(scale));
+        world.insert_resource(RapierConfiguration::default());
+        world.insert_resource(IntegrationParameters::default());
+        SystemStage::single(apply_time_scale.system()).run(&mut world);
+
+        let rapier = world.get_resource::<RapierConfiguration>().unwrap();
+        assert!(rapier.timestep_mode == TimestepMode::FixedTimestep);
+        (
+            delta,
+            world.get_resource::<IntegrationParameters>().unwrap().dt,
+        )
+    }
+
+    #[test]
+    fn unscaled_step_follows_the_frame() {
+        let (delta, dt) = step_with_scale(1.0);
+        assert!((dt - delta.min(TimeScale::MAX_FRAME_STEP)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn scale_above_one_speeds_physics_up() {
+        let (delta, dt) = step_with_scale(3.0);
+        let unscaled = delta.min(TimeScale::MAX_FRAME_STEP);
+        assert!(dt > unscaled);
+        assert!((dt - 3.0 * unscaled).abs() < 1e-6);
+    }
+
+    #[test]
+    fn scale_below_one_slows_physics_down() {
+        let (delta, dt) = step_with_scale(0.25);
+        assert!((dt - 0.25 * delta.min(TimeScale::MAX_FRAME_STEP)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn slow_frames_are_capped() {
+        assert_eq!(
+            TimeScale(2.0).physics_step(1.0),
+            2.0 * TimeScale::MAX_FRAME_STEP
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use bevy::{
+    diagnostic::{Diagnostics, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+};
+use bevy_prototype_lyon::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    components::{
+        chunk::{Chunk, SpawnedChunks},
+        debug::*,
+        types::{Player, Sturdiness},
+    },
+    config::GameConfig,
+    util::project2d,
+};
+
+pub fn spawn_debug_text(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn_bundle(TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(5.0),
+                    right: Val::Px(15.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            text: Text::with_section(
+                "",
+                TextStyle {
+                    font_size: 18.0,
+                    color: Color::YELLOW,
+                    font: asset_server.load("FiraSans-Bold.ttf"),
+                },
+                Default::default(),
+            ),
+            visible: Visible {
+                is_visible: false,
+                is_transparent: true,
+            },
+            ..Default::default()
+        })
+        .insert(DebugText {});
+}
+
+pub fn toggle_debug_overlay(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut overlay: ResMut<DebugOverlay>,
+    mut text: Query<&mut Visible, With<DebugText>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::F3) {
+        overlay.enabled = !overlay.enabled;
+        for mut visible in text.iter_mut() {
+            visible.is_visible = overlay.enabled;
+        }
+    }
+}
+
+pub fn debug_text(
+    overlay: Res<DebugOverlay>,
+    config: Res<GameConfig>,
+    diagnostics: Res<Diagnostics>,
+    entities: Query<Entity>,
+    bodies: Query<&RigidBodyActivation>,
+    player: Query<&Transform, With<Player>>,
+    mut text: Query<&mut Text, With<DebugText>>,
+) {
+    if !overlay.enabled {
+        return;
+    }
+    let fps = diagnostics
+        .get(FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.average())
+        .unwrap_or(0.0);
+    let awake = bodies.iter().filter(|b| !b.sleeping).count();
+    let player = match player.single() {
+        Ok(transform) => {
+            let pos = project2d(transform.translation);
+            format!(
+                "player: ({:.0}, {:.0}) in {:?}",
+                pos.x,
+                pos.y,
+                Chunk::containing_point(&pos, config.world.chunk_size)
+            )
+        }
+        Err(_) => "player: none".to_string(),
+    };
+
+    if let Ok(mut text) = text.single_mut() {
+        text.sections[0].value = format!(
+            "fps: {:.0}\nentities: {}\nbodies: {} ({} awake)\n{}",
+            fps,
+            entities.iter().count(),
+            bodies.iter().count(),
+            awake,
+            player
+        );
+    }
+}
+
+/// The convex pieces of a collider, in its body's local space.
+fn convex_pieces(shape: &ColliderShape) -> Vec<Vec<Vec2>> {
+    if let Some(compound) = shape.as_compound() {
+        compound
+            .shapes()
+            .iter()
+            .flat_map(|(isometry, part)| {
+                convex_pieces(part)
+                    .into_iter()
+                    .map(move |piece| {
+                        piece
+                            .into_iter()
+                            .map(|p| {
+                                let p = isometry * Point::new(p.x, p.y);
+                                Vec2::new(p.x, p.y)
+                            })
+                            .collect()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    } else {
+        shape
+            .as_convex_polygon()
+            .map(|polygon| {
+                polygon
+                    .points()
+                    .iter()
+                    .map(|p| Vec2::new(p.x, p.y))
+                    .collect()
+            })
+            .into_iter()
+            .collect()
+    }
+}
+
+fn spawn_layer<T: Geometry>(commands: &mut Commands, shapes: &[T], color: Color) {
+    if shapes.is_empty() {
+        return;
+    }
+    let mut builder = GeometryBuilder::new();
+    for shape in shapes {
+        builder.add(shape);
+    }
+    commands
+        .spawn_bundle(builder.build(
+            ShapeColors {
+                main: color,
+                outline: color,
+            },
+            DrawMode::Stroke(StrokeOptions::default()),
+            Transform::from_xyz(0.0, 0.0, 1.0),
+        ))
+        .insert(DebugShape {});
+}
+
+/// Redraw the chunk grid, collider outlines, velocity vectors and sturdiness labels.
+pub fn debug_shapes(
+    mut commands: Commands,
+    overlay: Res<DebugOverlay>,
+    config: Res<GameConfig>,
+    asset_server: Res<AssetServer>,
+    spawned_chunks: Res<SpawnedChunks>,
+    previous: Query<Entity, With<DebugShape>>,
+    bodies: Query<(&Transform, &ColliderShape, &RigidBodyVelocity, &Sturdiness)>,
+) {
+    for entity in previous.iter() {
+        commands.entity(entity).despawn();
+    }
+    if !overlay.enabled {
+        return;
+    }
+
+    let size = config.world.chunk_size;
+    let chunks = spawned_chunks
+        .0
+        .iter()
+        .map(|chunk| {
+            let (lo, hi) = (chunk.bottom_left(), chunk.top_right(size));
+            shapes::Polygon {
+                points: vec![lo, Vec2::new(hi.x, lo.y), hi, Vec2::new(lo.x, hi.y)],
+                closed: true,
+            }
+        })
+        .collect::<Vec<_>>();
+    spawn_layer(&mut commands, &chunks, Color::DARK_GRAY);
+
+    let font = asset_server.load("FiraSans-Bold.ttf");
+    let mut colliders = vec![];
+    let mut velocities = vec![];
+    for (transform, shape, velocity, sturdiness) in bodies.iter() {
+        let pos = project2d(transform.translation);
+        for piece in convex_pieces(shape) {
+            colliders.push(shapes::Polygon {
+                points: piece
+                    .iter()
+                    .map(|p| project2d(transform.mul_vec3(p.extend(0.0))))
+                    .collect(),
+                closed: true,
+            });
+        }
+        let linvel: Vec2 = velocity.linvel.into();
+        velocities.push(shapes::Line(pos, pos + linvel));
+
+        commands
+            .spawn_bundle(Text2dBundle {
+                text: Text::with_section(
+                    format!("{:.0}", sturdiness.0),
+                    TextStyle {
+                        font_size: 14.0,
+                        color: Color::YELLOW,
+                        font: font.clone(),
+                    },
+                    TextAlignment {
+                        horizontal: HorizontalAlign::Center,
+                        vertical: VerticalAlign::Center,
+                    },
+                ),
+                transform: Transform::from_xyz(pos.x, pos.y + 30.0, 1.0),
+                ..Default::default()
+            })
+            .insert(DebugShape {});
+    }
+    spawn_layer(&mut commands, &colliders, Color::GREEN);
+    spawn_layer(&mut commands, &velocities, Color::CYAN);
+}
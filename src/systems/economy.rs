@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    components::{damage::Category, events::DestroyedEvent, pickup::PickupKind},
+    config::GameConfig,
+    entities::pickup::PickupBundle,
+    util::from_polar,
+};
+
+/// Break destroyed asteroids into ore, according to what they are made of.
+pub fn drop_ore(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut destroyed: EventReader<DestroyedEvent>,
+) {
+    let mut rng = rand::thread_rng();
+    let economy = &config.economy;
+    for event in destroyed.iter() {
+        // The asteroid a fragment came from already gave up all its ore.
+        if event.category != Category::Asteroid || event.fragment {
+            continue;
+        }
+        let composition = match event
+            .material
+            .and_then(|material| economy.compositions.get(&material))
+        {
+            Some(composition) => composition,
+            None => continue,
+        };
+        let total = event.mass * economy.ore_per_mass;
+        for (ore, share) in composition.iter() {
+            let amount = (total * share).floor();
+            if amount < 1.0 {
+                continue;
+            }
+            let scatter = from_polar(
+                rng.gen_range(10.0..40.0),
+                rng.gen_range(0.0..std::f32::consts::TAU),
+            );
+            commands.spawn_bundle(PickupBundle::new(
+                event.position,
+                event.velocity * 0.5 + scatter,
+                PickupKind::Ore(*ore),
+                amount,
+                time.seconds_since_startup(),
+                config.loot.lifetime,
+            ));
+        }
+    }
+}
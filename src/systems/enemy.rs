@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    components::{
+        mission::Hunting,
+        ship::*,
+        types::{Enemy, Player},
+    },
+    config::GameConfig,
+    util::from_polar,
+};
+
+/// Steer enemies towards the player, or whatever they are hunting, and fire once they are lined up.
+pub fn enemy_ai(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    player: Query<&RigidBodyPosition, With<Player>>,
+    targets: Query<&RigidBodyPosition>,
+    mut enemies: Query<
+        (&RigidBodyPosition, &mut Controls, Option<&Hunting>),
+        (With<Enemy>, Without<Player>),
+    >,
+) {
+    let player: Option<Vec2> = player
+        .single()
+        .ok()
+        .map(|pos| pos.position.translation.into());
+
+    for (pos, mut controls, hunting) in enemies.iter_mut() {
+        let target = hunting
+            .and_then(|hunting| targets.get(hunting.0).ok())
+            .map(|pos| pos.position.translation.into())
+            .or(player);
+        controls.last_shot += time.delta_seconds();
+        controls.thrust = false;
+        controls.rotate = None;
+        controls.shoot = false;
+
+        let to_target = match target {
+            Some(target) => target - Vec2::from(pos.position.translation),
+            None => continue,
+        };
+        let dist = to_target.length();
+        if dist > config.enemy.aggro_range || dist == 0.0 {
+            continue;
+        }
+
+        let facing = from_polar(1.0, pos.position.rotation.angle());
+        let desired = to_target / dist;
+        let turn = facing.perp_dot(desired).atan2(facing.dot(desired));
+        controls.rotate = if turn > 0.05 {
+            Some(RotationDir::Left)
+        } else if turn < -0.05 {
+            Some(RotationDir::Right)
+        } else {
+            None
+        };
+
+        let aligned = turn.abs() < 0.2;
+        controls.thrust = aligned && dist > config.enemy.fire_range * 0.5;
+        controls.shoot = aligned
+            && dist < config.enemy.fire_range
+            && controls.last_shot >= config.weapon.cooldown * 2.0;
+        if controls.shoot {
+            controls.last_shot = 0.0;
+        }
+    }
+}
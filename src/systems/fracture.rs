@@ -0,0 +1,133 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+
+use crate::{
+    components::{damage::Category, events::DestroyedEvent, material::Material, types::Fragment},
+    config::{GameConfig, Shapes},
+    entities::asteroid::AsteroidBundle,
+    util::{polygon_area, polygon_centroid, rotate, split_polygon},
+};
+
+/// Scale applied to each piece, leaving a gap along the cuts.
+const PIECE_SHRINK: f32 = 0.95;
+
+/// Cut `outline` along `cuts` lines through `through`. The first runs at `angle`, and the rest
+/// fan out evenly around it, give or take a little.
+fn cut_outline(
+    rng: &mut impl Rng,
+    outline: &[(f32, f32)],
+    through: Vec2,
+    angle: f32,
+    cuts: u32,
+) -> Vec<Vec<(f32, f32)>> {
+    let mut pieces = vec![outline.to_vec()];
+    for i in 0..cuts {
+        let jitter = if i == 0 {
+            0.0
+        } else {
+            rng.gen_range(-0.2..0.2)
+        };
+        let angle = angle + PI * i as f32 / cuts as f32 + jitter;
+        let direction = Vec2::new(angle.cos(), angle.sin());
+        pieces = pieces
+            .iter()
+            .flat_map(|piece| split_polygon(piece, through, direction))
+            .collect();
+    }
+    pieces
+}
+
+/// Break destroyed asteroids into pieces of their own outline, cut along lines that all run
+/// through the point where they were last hit. Each piece keeps its share of the asteroid's
+/// motion, and is thrown clear of the impact by the blow.
+pub fn fracture_asteroids(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    shapes: Res<Shapes>,
+    mut destroyed: EventReader<DestroyedEvent>,
+) {
+    let rules = &config.asteroid;
+    if rules.fracture_cuts == 0 {
+        return;
+    }
+    let mut rng = rand::thread_rng();
+    // Pieces are as sturdy as a whole asteroid of the same size would be.
+    let full_area = polygon_area(&shapes.asteroid) * rules.size * rules.size;
+    for event in destroyed.iter() {
+        if event.category != Category::Asteroid {
+            continue;
+        }
+        let outline = match event.outline.first() {
+            Some(outline) => outline,
+            None => continue,
+        };
+        let material = event.material.unwrap_or(Material::Rock);
+
+        // Cut in the asteroid's own space, where its outline is.
+        let center = polygon_centroid(outline);
+        let hit = event.impact.map_or(center, |impact| {
+            rotate(impact.position - event.position, -event.rotation)
+        });
+        // The first cut runs from the impact through the middle.
+        let toward = center - hit;
+        let angle = if toward.length() > 0.01 {
+            toward.y.atan2(toward.x)
+        } else {
+            rng.gen_range(0.0..PI)
+        };
+        let mut pieces = cut_outline(&mut rng, outline, hit, angle, rules.fracture_cuts);
+        // A hit out near the edge can leave the cuts missing the outline altogether.
+        if pieces.len() < 2 {
+            pieces = cut_outline(&mut rng, outline, center, angle, rules.fracture_cuts);
+        }
+        let hit = event.position + rotate(hit, event.rotation);
+
+        // Sharing the blow's energy out by area gives every piece the same speed.
+        let total_area: f32 = pieces.iter().map(|piece| polygon_area(piece)).sum();
+        let energy = event.impact.map_or(0.0, |impact| impact.energy) * rules.fracture_energy;
+        let speed = (2.0 * energy / (total_area * material.props().density))
+            .sqrt()
+            .min(rules.max_fracture_speed);
+
+        for piece in pieces {
+            let area = polygon_area(&piece);
+            if area < rules.min_fragment_area {
+                continue;
+            }
+            // Center each piece on itself, so it spins about its own middle, and shrink it a
+            // little so the pieces don't start out touching and grinding on each other.
+            let offset = polygon_centroid(&piece);
+            let piece = piece
+                .iter()
+                .map(|(x, y)| ((x - offset.x) * PIECE_SHRINK, (y - offset.y) * PIECE_SHRINK))
+                .collect::<Vec<_>>();
+            let arm = rotate(offset, event.rotation);
+            let position = event.position + arm;
+            let away = position - hit;
+            let push = if away.length() > 0.0 {
+                away / away.length() * speed
+            } else {
+                Vec2::ZERO
+            };
+            let linvel = event.velocity + arm.perp() * event.angular_velocity + push;
+            match AsteroidBundle::from_outline(
+                piece,
+                Isometry::new(Vector::new(position.x, position.y), event.rotation).into(),
+                RigidBodyVelocity {
+                    linvel: linvel.into(),
+                    angvel: event.angular_velocity,
+                },
+                material,
+                rules.sturdiness * (area / full_area).sqrt(),
+            ) {
+                Ok(piece) => {
+                    commands.spawn_bundle(piece).insert(Fragment {});
+                }
+                Err(err) => debug!("Dropping asteroid piece: {}", err),
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::components::{gravity::GravityWell, types::Sturdiness};
+
+/// Pull bodies towards the gravity wells they are in range of.
+/// Anything that reaches the edge of a black hole is destroyed.
+pub fn gravity(
+    params: Res<IntegrationParameters>,
+    wells: Query<(&GravityWell, &RigidBodyPosition)>,
+    mut bodies: Query<
+        (
+            &RigidBodyPosition,
+            &mut RigidBodyVelocity,
+            Option<&mut Sturdiness>,
+        ),
+        Without<GravityWell>,
+    >,
+) {
+    let wells = wells
+        .iter()
+        .map(|(well, pos)| {
+            let center: Vec2 = pos.position.translation.into();
+            (well, center)
+        })
+        .collect::<Vec<_>>();
+    if wells.is_empty() {
+        return;
+    }
+    // Changing velocities directly, rather than adding forces, keeps the pull independent of
+    // mass and leaves ship thrust alone.
+    let dt = params.dt;
+    for (pos, mut vel, mut sturdiness) in bodies.iter_mut() {
+        let position: Vec2 = pos.position.translation.into();
+        let mut dv = Vec2::ZERO;
+        for (well, center) in wells.iter() {
+            let offset = position - *center;
+            if let Some(acceleration) = well.acceleration(offset) {
+                dv += acceleration * dt;
+            }
+            if well.swallows && offset.length_squared() < well.radius * well.radius {
+                if let Some(sturdiness) = sturdiness.as_mut() {
+                    sturdiness.0 = 0.0;
+                }
+            }
+        }
+        if dv != Vec2::ZERO {
+            let linvel = Vec2::from(vel.linvel) + dv;
+            vel.linvel = linvel.into();
+        }
+    }
+}
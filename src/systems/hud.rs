@@ -0,0 +1,622 @@
+use bevy::{
+    prelude::*,
+    render::{
+        camera::{Camera, OrthographicProjection},
+        render_graph::base::camera,
+    },
+    utils::HashMap,
+};
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    components::{
+        boss::{BossBar, BossBarFill, BossBarText},
+        chunk::{Chunk, WorldSeed},
+        damage::Category,
+        difficulty::Difficulty,
+        economy::CargoHold,
+        hud::*,
+        mission::{Missions, WaypointMarker},
+        pickup::{Credits, PowerUps},
+        ship::{Fuel, Weapon},
+        types::{MaxSturdiness, Player, Score, SelectedHull, Sturdiness},
+    },
+    config::{GameConfig, HullCatalogue},
+    entities::hud::{ThreatIndicatorBundle, WaypointMarkerBundle},
+    util::{project2d, time_to_impact},
+};
+
+const BAR_WIDTH: f32 = 150.0;
+const BAR_HEIGHT: f32 = 10.0;
+const MINIMAP_SIZE: f32 = 150.0;
+const BLIP_SIZE: f32 = 4.0;
+const MINIMAP_BLIPS: usize = 64;
+
+fn spawn_bar(
+    parent: &mut ChildBuilder,
+    font: Handle<Font>,
+    label: &str,
+    bar: HudBar,
+    background: Handle<ColorMaterial>,
+    fill: Handle<ColorMaterial>,
+    transparent: Handle<ColorMaterial>,
+) {
+    parent
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                margin: Rect {
+                    top: Val::Px(4.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            material: transparent,
+            ..Default::default()
+        })
+        .with_children(|row| {
+            row.spawn_bundle(TextBundle {
+                style: Style {
+                    size: Size::new(Val::Px(50.0), Val::Auto),
+                    ..Default::default()
+                },
+                text: Text::with_section(
+                    label,
+                    TextStyle {
+                        font_size: 16.0,
+                        color: Color::WHITE,
+                        font,
+                    },
+                    Default::default(),
+                ),
+                ..Default::default()
+            });
+            row.spawn_bundle(NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Px(BAR_WIDTH), Val::Px(BAR_HEIGHT)),
+                    ..Default::default()
+                },
+                material: background,
+                ..Default::default()
+            })
+            .with_children(|bar_background| {
+                bar_background
+                    .spawn_bundle(NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                            ..Default::default()
+                        },
+                        material: fill,
+                        ..Default::default()
+                    })
+                    .insert(HudBarFill(bar));
+            });
+        });
+}
+
+pub fn spawn_hud(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let hud_materials = HudMaterials {
+        panel: materials.add(Color::rgba(0.2, 0.2, 0.2, 0.6).into()),
+        hull: materials.add(Color::GREEN.into()),
+        fuel: materials.add(Color::ORANGE.into()),
+        asteroid: materials.add(Color::GRAY.into()),
+        enemy: materials.add(Color::RED.into()),
+        pickup: materials.add(Color::YELLOW.into()),
+        player: materials.add(Color::WHITE.into()),
+        boss: materials.add(Color::ORANGE_RED.into()),
+    };
+    let transparent = materials.add(Color::NONE.into());
+    let font = asset_server.load("FiraSans-Bold.ttf");
+
+    // Stats panel in the top left.
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(5.0),
+                    left: Val::Px(15.0),
+                    ..Default::default()
+                },
+                // UI layout runs bottom to top, so reverse it to stack children downwards.
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::FlexStart,
+                ..Default::default()
+            },
+            material: transparent.clone(),
+            ..Default::default()
+        })
+        .with_children(|panel| {
+            spawn_bar(
+                panel,
+                font.clone(),
+                "HULL",
+                HudBar::Hull,
+                hud_materials.panel.clone(),
+                hud_materials.hull.clone(),
+                transparent.clone(),
+            );
+            spawn_bar(
+                panel,
+                font.clone(),
+                "FUEL",
+                HudBar::Fuel,
+                hud_materials.panel.clone(),
+                hud_materials.fuel.clone(),
+                transparent.clone(),
+            );
+            panel
+                .spawn_bundle(TextBundle {
+                    style: Style {
+                        margin: Rect {
+                            top: Val::Px(4.0),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    text: Text::with_section(
+                        "",
+                        TextStyle {
+                            font_size: 18.0,
+                            color: Color::WHITE,
+                            font: font.clone(),
+                        },
+                        Default::default(),
+                    ),
+                    ..Default::default()
+                })
+                .insert(HudText {});
+        });
+
+    // Minimap in the bottom right, with the player at its center.
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    bottom: Val::Px(15.0),
+                    right: Val::Px(15.0),
+                    ..Default::default()
+                },
+                size: Size::new(Val::Px(MINIMAP_SIZE), Val::Px(MINIMAP_SIZE)),
+                ..Default::default()
+            },
+            material: hud_materials.panel.clone(),
+            ..Default::default()
+        })
+        .insert(Minimap {})
+        .with_children(|minimap| {
+            let blip_style = |x: f32, y: f32| Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(x - BLIP_SIZE / 2.0),
+                    bottom: Val::Px(y - BLIP_SIZE / 2.0),
+                    ..Default::default()
+                },
+                size: Size::new(Val::Px(BLIP_SIZE), Val::Px(BLIP_SIZE)),
+                ..Default::default()
+            };
+            minimap.spawn_bundle(NodeBundle {
+                style: blip_style(MINIMAP_SIZE / 2.0, MINIMAP_SIZE / 2.0),
+                material: hud_materials.player.clone(),
+                ..Default::default()
+            });
+            for _ in 0..MINIMAP_BLIPS {
+                minimap
+                    .spawn_bundle(NodeBundle {
+                        style: blip_style(0.0, 0.0),
+                        material: hud_materials.asteroid.clone(),
+                        visible: Visible {
+                            is_visible: false,
+                            is_transparent: false,
+                        },
+                        ..Default::default()
+                    })
+                    .insert(MinimapBlip {});
+            }
+        });
+
+    // Boss health bar along the top, hidden until a boss is close.
+    let hidden = Visible {
+        is_visible: false,
+        is_transparent: true,
+    };
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(30.0),
+                    left: Val::Percent(35.0),
+                    ..Default::default()
+                },
+                size: Size::new(Val::Percent(30.0), Val::Auto),
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: transparent.clone(),
+            visible: hidden.clone(),
+            ..Default::default()
+        })
+        .insert(BossBar {})
+        .with_children(|panel| {
+            panel
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section(
+                        "",
+                        TextStyle {
+                            font_size: 18.0,
+                            color: Color::ORANGE_RED,
+                            font: font.clone(),
+                        },
+                        Default::default(),
+                    ),
+                    visible: hidden.clone(),
+                    ..Default::default()
+                })
+                .insert(BossBar {})
+                .insert(BossBarText {});
+            panel
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(100.0), Val::Px(BAR_HEIGHT)),
+                        margin: Rect {
+                            top: Val::Px(4.0),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    material: hud_materials.panel.clone(),
+                    visible: hidden.clone(),
+                    ..Default::default()
+                })
+                .insert(BossBar {})
+                .with_children(|bar| {
+                    bar.spawn_bundle(NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                            ..Default::default()
+                        },
+                        material: hud_materials.boss.clone(),
+                        visible: hidden.clone(),
+                        ..Default::default()
+                    })
+                    .insert(BossBar {})
+                    .insert(BossBarFill {});
+                });
+        });
+
+    commands.insert_resource(hud_materials);
+}
+
+pub fn hud_bars(
+    hulls: Res<HullCatalogue>,
+    selected: Res<SelectedHull>,
+    player: Query<(&Sturdiness, &MaxSturdiness, &Fuel), With<Player>>,
+    mut fills: Query<(&HudBarFill, &mut Style)>,
+) {
+    let fuel_capacity = hulls
+        .hulls
+        .get(selected.0)
+        .map_or(0.0, |hull| hull.ship.fuel);
+    let (hull, fuel) = match player.single() {
+        Ok((sturdiness, max, fuel)) => (
+            sturdiness.0 / max.0,
+            if fuel_capacity > 0.0 {
+                fuel.0 / fuel_capacity
+            } else {
+                0.0
+            },
+        ),
+        Err(_) => (0.0, 0.0),
+    };
+
+    for (fill, mut style) in fills.iter_mut() {
+        let fraction = match fill.0 {
+            HudBar::Hull => hull,
+            HudBar::Fuel => fuel,
+        };
+        let width = Val::Percent(fraction.clamp(0.0, 1.0) * 100.0);
+        // Only touch the style when it changes, so the UI isn't laid out again every frame.
+        if style.size.width != width {
+            style.size.width = width;
+        }
+    }
+}
+
+pub fn hud_text(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    seed: Res<WorldSeed>,
+    difficulty: Res<Difficulty>,
+    score: Res<Score>,
+    lives: Res<Lives>,
+    credits: Res<Credits>,
+    missions: Res<Missions>,
+    player: Query<
+        (
+            &RigidBodyPosition,
+            &RigidBodyVelocity,
+            &Weapon,
+            Option<&PowerUps>,
+            Option<&CargoHold>,
+        ),
+        With<Player>,
+    >,
+    mut text: Query<&mut Text, With<HudText>>,
+) {
+    let status = match player.single() {
+        Ok((pos, vel, weapon, power_ups, cargo)) => {
+            let position: Vec2 = pos.position.translation.into();
+            let speed = Vec2::from(vel.linvel).length();
+            // Compass heading: clockwise from straight up.
+            let heading = (-pos.position.rotation.angle().to_degrees()).rem_euclid(360.0);
+            let size = config.world.chunk_size;
+            let sector = Chunk::containing_point(&position, size).grid_coords(size);
+            let biome = config
+                .biomes
+                .sample(seed.0, sector.0, sector.1)
+                .map_or("uncharted", |biome| biome.kind.name());
+            let elapsed = (time.seconds_since_startup() - difficulty.run_started) as f32;
+            let level = config.difficulty.level(position.length(), elapsed);
+            let ammo = match weapon.ammo {
+                Some(ammo) => ammo.to_string(),
+                None => "unlimited".to_string(),
+            };
+            let cargo = cargo.map_or(String::new(), |cargo| {
+                format!("\ncargo: {:.0}/{:.0}", cargo.used(), cargo.capacity)
+            });
+            let power_ups = power_ups.map_or(String::new(), |power_ups| {
+                power_ups
+                    .0
+                    .iter()
+                    .map(|(power_up, remaining)| {
+                        format!("\n{}: {:.0}s", power_up.name(), remaining.ceil())
+                    })
+                    .collect()
+            });
+            format!(
+                "speed: {:.0}\nheading: {:03.0}\nweapon: {} ({})\nsector: ({}, {}) {}\ndanger: {:.1} ({}){}{}",
+                speed,
+                heading,
+                weapon.name,
+                ammo,
+                sector.0,
+                sector.1,
+                biome,
+                level,
+                difficulty.preset.name(),
+                cargo,
+                power_ups
+            )
+        }
+        Err(_) => String::new(),
+    };
+
+    let mut mission = missions.active.as_ref().map_or(String::new(), |mission| {
+        format!("\n\nmission: {}", mission.describe())
+    });
+    if let Some((notice, until)) = &missions.notice {
+        if time.seconds_since_startup() < *until {
+            mission = format!("{}\n{}", mission, notice);
+        }
+    }
+
+    if let Ok(mut text) = text.single_mut() {
+        text.sections[0].value = format!(
+            "Score: {}\nLives: {}\nCredits: {}\n{}{}",
+            score.0, lives.0, credits.0, status, mission
+        );
+    }
+}
+
+/// Place minimap blips for everything near the player, colored by category.
+pub fn minimap(
+    config: Res<GameConfig>,
+    materials: Res<HudMaterials>,
+    player: Query<&Transform, With<Player>>,
+    others: Query<(&Transform, &Category), Without<Player>>,
+    mut blips: Query<(&mut Style, &mut Visible, &mut Handle<ColorMaterial>), With<MinimapBlip>>,
+) {
+    let range = config.hud.minimap_range;
+    let center = player.single().ok().map(|t| project2d(t.translation));
+    let mut nearby = others.iter().filter_map(|(transform, category)| {
+        let material = match category {
+            Category::Asteroid => &materials.asteroid,
+            Category::Enemy => &materials.enemy,
+            Category::Pickup => &materials.pickup,
+            _ => return None,
+        };
+        let offset = project2d(transform.translation) - center?;
+        if offset.x.abs() > range || offset.y.abs() > range {
+            return None;
+        }
+        let on_map = (offset / range + Vec2::ONE) * (MINIMAP_SIZE / 2.0);
+        Some((on_map, material))
+    });
+
+    for (mut style, mut visible, mut material) in blips.iter_mut() {
+        match nearby.next() {
+            Some((on_map, blip_material)) => {
+                style.position.left = Val::Px(on_map.x - BLIP_SIZE / 2.0);
+                style.position.bottom = Val::Px(on_map.y - BLIP_SIZE / 2.0);
+                if *material != *blip_material {
+                    *material = blip_material.clone();
+                }
+                if !visible.is_visible {
+                    visible.is_visible = true;
+                }
+            }
+            None => {
+                if visible.is_visible {
+                    visible.is_visible = false;
+                }
+            }
+        }
+    }
+}
+
+pub fn spawn_threat_indicators(mut commands: Commands) {
+    for urgency in Urgency::ALL.iter() {
+        for _ in 0..Urgency::CAPACITY {
+            commands.spawn_bundle(ThreatIndicatorBundle::new(*urgency));
+        }
+    }
+}
+
+/// Point arrows from the edge of the screen at off-screen asteroids and enemies on course to hit
+/// the player. Arrows grow and change color as the time to impact runs out.
+pub fn threat_indicators(
+    config: Res<GameConfig>,
+    windows: Res<Windows>,
+    cameras: Query<(&Transform, &Camera, &OrthographicProjection)>,
+    player: Query<(&RigidBodyPosition, &RigidBodyVelocity, &ColliderShape), With<Player>>,
+    bodies: Query<
+        (
+            &RigidBodyPosition,
+            &RigidBodyVelocity,
+            &ColliderShape,
+            &Category,
+        ),
+        Without<Player>,
+    >,
+    mut indicators: Query<(&ThreatIndicator, &mut Transform, &mut Visible), Without<Camera>>,
+) {
+    let warning_time = config.hud.threat_warning_time;
+    let window = windows.get_primary();
+    let camera = cameras
+        .iter()
+        .find(|(_, cam, _)| cam.name == Some(camera::CAMERA_2D.to_string()));
+
+    let mut threats: HashMap<Urgency, Vec<(Vec2, f32)>> = HashMap::default();
+    let mut view = None;
+    if let (Some(window), Some(camera), Ok(ship)) = (window, camera, player.single()) {
+        let (cam_transform, _, projection) = camera;
+        let (ship_pos, ship_vel, ship_shape) = ship;
+        let center = project2d(cam_transform.translation);
+        let half_size = Vec2::new(window.width(), window.height()) / 2.0 * projection.scale;
+        view = Some((center, half_size, projection.scale));
+
+        let ship_position: Vec2 = ship_pos.position.translation.into();
+        let ship_velocity: Vec2 = ship_vel.linvel.into();
+        let ship_radius = ship_shape.compute_local_bounding_sphere().radius();
+
+        let mut incoming = bodies
+            .iter()
+            .filter(|(.., category)| matches!(category, Category::Asteroid | Category::Enemy))
+            .filter_map(|(pos, vel, shape, _)| {
+                let position: Vec2 = pos.position.translation.into();
+                let from_center = (position - center).abs();
+                if from_center.x < half_size.x && from_center.y < half_size.y {
+                    return None;
+                }
+                let velocity: Vec2 = vel.linvel.into();
+                let radius = shape.compute_local_bounding_sphere().radius() + ship_radius;
+                time_to_impact(position - ship_position, velocity - ship_velocity, radius)
+                    .filter(|t| *t <= warning_time)
+                    .map(|t| (position, t))
+            })
+            .collect::<Vec<_>>();
+        // Indicators pop the soonest threats first, so those win if there aren't enough to go round.
+        incoming.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        for (position, t) in incoming {
+            threats
+                .entry(Urgency::from_time(t, warning_time))
+                .or_default()
+                .push((position, t));
+        }
+    }
+
+    for (indicator, mut transform, mut visible) in indicators.iter_mut() {
+        let threat = threats
+            .get_mut(&indicator.urgency)
+            .and_then(|threats| threats.pop());
+        let ((center, half_size, scale), (position, t)) = match (view, threat) {
+            (Some(view), Some(threat)) => (view, threat),
+            _ => {
+                if visible.is_visible {
+                    visible.is_visible = false;
+                }
+                continue;
+            }
+        };
+
+        // Slide along the line from the center of the screen to the threat until it meets the edge.
+        let direction = position - center;
+        let inset = half_size - Vec2::splat(config.hud.threat_margin * scale);
+        let to_edge = (inset / direction.abs()).min_element();
+        let on_edge = center + direction * to_edge;
+
+        let size = scale * (2.0 - t / warning_time);
+        transform.translation = on_edge.extend(transform.translation.z);
+        transform.rotation =
+            Quat::from_rotation_z(direction.y.atan2(direction.x) - std::f32::consts::FRAC_PI_2);
+        transform.scale = Vec3::new(size, size, 1.0);
+        visible.is_visible = true;
+    }
+}
+
+pub fn spawn_waypoint_marker(mut commands: Commands) {
+    commands.spawn_bundle(WaypointMarkerBundle::new(Color::CYAN));
+}
+
+/// Point at the current mission's waypoint: from the edge of the screen while it is out of view,
+/// and from just above it once it comes into view.
+pub fn waypoint_marker(
+    config: Res<GameConfig>,
+    windows: Res<Windows>,
+    missions: Res<Missions>,
+    cameras: Query<(&Transform, &Camera, &OrthographicProjection)>,
+    mut marker: Query<(&mut Transform, &mut Visible), (With<WaypointMarker>, Without<Camera>)>,
+) {
+    let (mut transform, mut visible) = match marker.single_mut() {
+        Ok(marker) => marker,
+        Err(_) => return,
+    };
+    let waypoint = missions
+        .active
+        .as_ref()
+        .and_then(|mission| mission.waypoint);
+    let camera = cameras
+        .iter()
+        .find(|(_, cam, _)| cam.name == Some(camera::CAMERA_2D.to_string()));
+    let (waypoint, window, (cam_transform, _, projection)) =
+        match (waypoint, windows.get_primary(), camera) {
+            (Some(waypoint), Some(window), Some(camera)) => (waypoint, window, camera),
+            _ => {
+                if visible.is_visible {
+                    visible.is_visible = false;
+                }
+                return;
+            }
+        };
+
+    let scale = projection.scale;
+    let center = project2d(cam_transform.translation);
+    let half_size = Vec2::new(window.width(), window.height()) / 2.0 * scale;
+    let direction = waypoint - center;
+    let inset = half_size - Vec2::splat(config.hud.threat_margin * scale);
+    let (position, angle) = if direction.x.abs() > inset.x || direction.y.abs() > inset.y {
+        let to_edge = (inset / direction.abs()).min_element();
+        (
+            center + direction * to_edge,
+            direction.y.atan2(direction.x) - std::f32::consts::FRAC_PI_2,
+        )
+    } else {
+        // Hover over the waypoint, pointing down at it.
+        (
+            waypoint + Vec2::new(0.0, 20.0 * scale),
+            std::f32::consts::PI,
+        )
+    };
+    transform.translation = position.extend(transform.translation.z);
+    transform.rotation = Quat::from_rotation_z(angle);
+    transform.scale = Vec3::new(1.5 * scale, 1.5 * scale, 1.0);
+    visible.is_visible = true;
+}
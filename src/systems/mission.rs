@@ -0,0 +1,339 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::{distributions::WeightedIndex, prelude::Distribution, rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    components::{
+        chunk::{Chunk, WorldSeed},
+        damage::Category,
+        events::DestroyedEvent,
+        mission::{CargoDrone, Hunting, Mission, MissionKind, Missions, Objective},
+        pickup::Credits,
+        ship::Fuel,
+        station::Station,
+        types::{Enemy, Player, Score},
+    },
+    config::{GameConfig, Shapes},
+    entities::{boss::BossPartBundle, enemy::EnemyBundle, mission::CargoDroneBundle},
+    progression::Progression,
+    util::{from_polar, hash_coords},
+};
+
+/// Seconds the HUD shows how a mission ended.
+const NOTICE_TIME: f64 = 5.0;
+
+/// Start each run without a mission, clearing away anything left over from the last one.
+pub fn reset_missions(
+    mut commands: Commands,
+    mut missions: ResMut<Missions>,
+    drones: Query<Entity, With<CargoDrone>>,
+    hunters: Query<Entity, With<Hunting>>,
+) {
+    *missions = Missions::default();
+    for entity in drones.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    for entity in hunters.iter() {
+        commands.entity(entity).remove::<Hunting>();
+    }
+}
+
+/// Hand out a mission when the player reaches a sector that hasn't offered one yet.
+/// Each sector's mission is picked from the world seed, so it is the same every time.
+pub fn offer_missions(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    seed: Res<WorldSeed>,
+    mut missions: ResMut<Missions>,
+    player: Query<&RigidBodyPosition, With<Player>>,
+    stations: Query<(Entity, &RigidBodyPosition), With<Station>>,
+) {
+    let now = time.seconds_since_startup();
+    if missions.active.is_some() || now < missions.next_offer {
+        return;
+    }
+    let position: Vec2 = match player.single() {
+        Ok(pos) => pos.position.translation.into(),
+        Err(_) => return,
+    };
+    let size = config.world.chunk_size;
+    let sector = Chunk::containing_point(&position, size).grid_coords(size);
+    if !missions.offered.insert(sector) {
+        return;
+    }
+
+    let rules = &config.missions;
+    let station = stations
+        .iter()
+        .map(|(entity, pos)| (entity, Vec2::from(pos.position.translation)))
+        .filter(|(_, station)| station.distance(position) <= rules.defend_range)
+        .min_by(|a, b| {
+            let a = a.1.distance_squared(position);
+            let b = b.1.distance_squared(position);
+            a.partial_cmp(&b).unwrap()
+        });
+    // There's nothing to defend without a station nearby.
+    let kinds = rules
+        .kinds
+        .iter()
+        .filter(|kind| kind.kind != MissionKind::Defend || station.is_some())
+        .collect::<Vec<_>>();
+    let mut rng = StdRng::seed_from_u64(hash_coords(seed.0, sector.0, sector.1));
+    let picked = match WeightedIndex::new(kinds.iter().map(|kind| kind.weight)) {
+        Ok(weights) => kinds[weights.sample(&mut rng)],
+        Err(_) => return,
+    };
+
+    let (near, far) = rules.waypoint_distance;
+    let waypoint = position
+        + from_polar(
+            rng.gen_range(near..=far),
+            rng.gen_range(0.0..std::f32::consts::TAU),
+        );
+    let (objective, waypoint) = match picked.kind {
+        MissionKind::Destroy => (
+            Objective::Destroy {
+                remaining: rules.destroy_count,
+            },
+            None,
+        ),
+        MissionKind::Escort => {
+            let start = position + from_polar(80.0, rng.gen_range(0.0..std::f32::consts::TAU));
+            let drone = match CargoDroneBundle::new(start, waypoint, rules.escort_sturdiness) {
+                Ok(drone) => commands.spawn_bundle(drone).id(),
+                Err(err) => {
+                    warn!("Could not spawn cargo drone: {}", err);
+                    return;
+                }
+            };
+            (Objective::Escort { drone }, Some(waypoint))
+        }
+        MissionKind::Reach => (Objective::Reach, Some(waypoint)),
+        MissionKind::Defend => {
+            let (station, at) = match station {
+                Some(station) => station,
+                None => return,
+            };
+            (
+                Objective::Defend {
+                    station,
+                    remaining: rules.defend_time,
+                    integrity: rules.defend_integrity,
+                    next_raid: 0.0,
+                },
+                Some(at),
+            )
+        }
+        MissionKind::Boss => {
+            let order = &config.bosses.order;
+            if order.is_empty() {
+                return;
+            }
+            let kind = order[rng.gen_range(0..order.len())];
+            let boss = BossPartBundle::spawn_boss(&mut commands, kind, waypoint, &config.bosses);
+            (Objective::Boss { boss }, Some(waypoint))
+        }
+    };
+    info!("New mission: {}", picked.kind.name());
+    missions.notice = Some((
+        format!("new mission: {}", picked.kind.name()),
+        now + NOTICE_TIME,
+    ));
+    missions.active = Some(Mission {
+        kind: picked.kind,
+        objective,
+        waypoint,
+        credits: picked.credits,
+        score: picked.score,
+    });
+}
+
+/// Move the escorted drone along its route, as long as the player stays close enough to guard it.
+pub fn escort_drones(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    player: Query<&RigidBodyPosition, With<Player>>,
+    mut drones: Query<(&CargoDrone, &RigidBodyPosition, &mut RigidBodyVelocity)>,
+) {
+    let escort: Option<Vec2> = player
+        .single()
+        .ok()
+        .map(|pos| pos.position.translation.into());
+    let rules = &config.missions;
+    // Ease into the new velocity rather than snapping to it.
+    let blend = (2.0 * time.delta_seconds()).min(1.0);
+    for (drone, pos, mut vel) in drones.iter_mut() {
+        let position: Vec2 = pos.position.translation.into();
+        let guarded = escort.is_some_and(|escort| escort.distance(position) <= rules.escort_range);
+        let offset = drone.destination - position;
+        let desired = if guarded && offset.length() > 0.0 {
+            offset.normalize() * rules.escort_speed
+        } else {
+            Vec2::ZERO
+        };
+        let current: Vec2 = vel.linvel.into();
+        vel.linvel = (current + (desired - current) * blend).into();
+        vel.angvel *= 1.0 - blend;
+    }
+}
+
+/// Advance the current mission, and pay out or call it off once it is decided.
+pub fn track_missions(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    shapes: Res<Shapes>,
+    mut missions: ResMut<Missions>,
+    mut score: ResMut<Score>,
+    mut credits: ResMut<Credits>,
+    mut progression: ResMut<Progression>,
+    mut destroyed: EventReader<DestroyedEvent>,
+    player: Query<(&RigidBodyPosition, &Fuel), With<Player>>,
+    positions: Query<&RigidBodyPosition>,
+    hunters: Query<(Entity, &Hunting, &RigidBodyPosition), With<Enemy>>,
+) {
+    let destroyed = destroyed.iter().collect::<Vec<_>>();
+    let now = time.seconds_since_startup();
+    let dt = time.delta_seconds();
+    let rules = &config.missions;
+    let mission = match missions.active.as_mut() {
+        Some(mission) => mission,
+        None => return,
+    };
+    let position_of = |entity| -> Option<Vec2> {
+        positions
+            .get(entity)
+            .ok()
+            .map(|pos| pos.position.translation.into())
+    };
+    let was_destroyed = |entity| destroyed.iter().any(|event| event.entity == entity);
+    let waypoint = mission.waypoint;
+    let arrived = |position: Vec2| {
+        waypoint.is_some_and(|waypoint| waypoint.distance(position) <= rules.arrival_radius)
+    };
+
+    let outcome = match &mut mission.objective {
+        Objective::Destroy { remaining } => {
+            let asteroids = destroyed
+                .iter()
+                .filter(|event| event.category == Category::Asteroid && !event.fragment)
+                .count() as u32;
+            *remaining = remaining.saturating_sub(asteroids);
+            if *remaining == 0 {
+                Some(true)
+            } else {
+                None
+            }
+        }
+        Objective::Escort { drone } => {
+            if was_destroyed(*drone) {
+                Some(false)
+            } else {
+                match position_of(*drone) {
+                    Some(position) if arrived(position) => Some(true),
+                    Some(_) => None,
+                    None => Some(false),
+                }
+            }
+        }
+        Objective::Reach => match player.single() {
+            Ok((pos, _)) if arrived(pos.position.translation.into()) => Some(true),
+            Ok((_, fuel)) if fuel.0 <= 0.0 => Some(false),
+            _ => None,
+        },
+        Objective::Defend {
+            station,
+            remaining,
+            integrity,
+            next_raid,
+        } => match position_of(*station) {
+            Some(at) => {
+                *next_raid -= dt;
+                if *next_raid <= 0.0 {
+                    *next_raid += rules.raid_interval;
+                    let mut rng = rand::thread_rng();
+                    for _ in 0..rules.raiders {
+                        let offset = from_polar(
+                            config.enemy.aggro_range * 0.8,
+                            rng.gen_range(0.0..std::f32::consts::TAU),
+                        );
+                        match EnemyBundle::new((at + offset).into(), &config.enemy, &shapes) {
+                            Ok(raider) => {
+                                commands.spawn_bundle(raider).insert(Hunting(*station));
+                            }
+                            Err(err) => warn!("Could not spawn raider: {}", err),
+                        }
+                    }
+                }
+                let attacking = hunters
+                    .iter()
+                    .filter(|(_, hunting, pos)| {
+                        hunting.0 == *station
+                            && at.distance(pos.position.translation.into())
+                                <= config.enemy.fire_range
+                    })
+                    .count();
+                *integrity -= attacking as f32 * rules.raider_damage * dt;
+                *remaining -= dt;
+                if *integrity <= 0.0 {
+                    Some(false)
+                } else if *remaining <= 0.0 {
+                    Some(true)
+                } else {
+                    None
+                }
+            }
+            None => Some(false),
+        },
+        Objective::Boss { boss } => {
+            if was_destroyed(*boss) {
+                Some(true)
+            } else {
+                match position_of(*boss) {
+                    // The boss moves, so keep the waypoint on it.
+                    Some(position) => {
+                        mission.waypoint = Some(position);
+                        None
+                    }
+                    None => Some(false),
+                }
+            }
+        }
+    };
+
+    let completed = match outcome {
+        Some(completed) => completed,
+        None => return,
+    };
+    match mission.objective {
+        // A delivered drone has done its job, and a lost one may already be gone.
+        Objective::Escort { drone } if !was_destroyed(drone) && position_of(drone).is_some() => {
+            commands.entity(drone).despawn_recursive();
+        }
+        // Raiders go back to chasing the player once the station is no longer the mission.
+        Objective::Defend { station, .. } => {
+            for (entity, hunting, _) in hunters.iter() {
+                if hunting.0 == station {
+                    commands.entity(entity).remove::<Hunting>();
+                }
+            }
+        }
+        _ => (),
+    }
+    let notice = if completed {
+        score.0 += mission.score;
+        credits.0 += mission.credits;
+        progression.missions_completed += 1;
+        format!(
+            "mission complete: +{} credits, +{} score",
+            mission.credits, mission.score
+        )
+    } else {
+        "mission failed".to_string()
+    };
+    info!("{}", notice);
+    missions.notice = Some((notice, now + NOTICE_TIME));
+    missions.active = None;
+    missions.next_offer = now + rules.interval as f64;
+}
@@ -1,3 +1,22 @@
+pub mod biome;
+pub mod boss;
+pub mod camera;
 pub mod common;
+pub mod condition;
+pub mod config;
+pub mod console;
+pub mod debug;
+pub mod economy;
+pub mod enemy;
+pub mod fracture;
+pub mod gravity;
+pub mod hud;
+pub mod mission;
+pub mod particle;
+pub mod pickup;
 pub mod player;
+pub mod run;
+pub mod shield;
 pub mod ship;
+pub mod starfield;
+pub mod station;
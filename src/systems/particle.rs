@@ -0,0 +1,164 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+
+use crate::{
+    components::{
+        damage::Category,
+        events::{DestroyedEvent, ImpactEvent},
+        particle::*,
+    },
+    entities::particle::ParticleBundle,
+    util::{from_polar, rotate},
+};
+
+pub fn spawn_particle_pool(mut commands: Commands, mut pool: ResMut<ParticlePool>) {
+    for kind in ParticleKind::ALL.iter() {
+        let free = (0..kind.capacity())
+            .map(|_| commands.spawn_bundle(ParticleBundle::new(*kind)).id())
+            .collect();
+        pool.free.insert(*kind, free);
+    }
+}
+
+/// Take a free particle of the given kind and launch it. Does nothing if the pool is exhausted.
+fn emit(
+    pool: &mut ParticlePool,
+    particles: &mut Query<(&mut Particle, &mut Transform, &mut Visible)>,
+    kind: ParticleKind,
+    position: Vec2,
+    velocity: Vec2,
+    rng: &mut impl Rng,
+) {
+    let entity = match pool.free.get_mut(&kind).and_then(|free| free.pop()) {
+        Some(entity) => entity,
+        None => return,
+    };
+    if let Ok((mut particle, mut transform, mut visible)) = particles.get_mut(entity) {
+        let (min, max) = kind.lifetime();
+        particle.velocity = velocity;
+        particle.age = 0.0;
+        particle.lifetime = rng.gen_range(min..max);
+        transform.translation = position.extend(transform.translation.z);
+        transform.rotation = Quat::from_rotation_z(rng.gen_range(0.0..std::f32::consts::TAU));
+        transform.scale = Vec3::ONE;
+        visible.is_visible = true;
+    }
+}
+
+pub fn emit_particles(
+    time: Res<Time>,
+    mut pool: ResMut<ParticlePool>,
+    mut bursts: EventReader<ParticleBurst>,
+    mut emitters: Query<(&mut ParticleEmitter, &RigidBodyPosition, &RigidBodyVelocity)>,
+    mut particles: Query<(&mut Particle, &mut Transform, &mut Visible)>,
+) {
+    let mut rng = rand::thread_rng();
+
+    for (mut emitter, pos, vel) in emitters.iter_mut() {
+        if !emitter.active {
+            emitter.accumulator = 0.0;
+            continue;
+        }
+        emitter.accumulator += emitter.rate * time.delta_seconds();
+        let angle = pos.position.rotation.angle();
+        let origin = Vec2::from(pos.position.translation) + rotate(emitter.offset, angle);
+        let body_velocity: Vec2 = vel.linvel.into();
+        while emitter.accumulator >= 1.0 {
+            emitter.accumulator -= 1.0;
+            let spread = rng.gen_range(-emitter.spread..=emitter.spread);
+            // Vary the speed too, so the exhaust flickers instead of forming a solid line.
+            let speed = emitter.speed * rng.gen_range(0.5..1.0);
+            let direction = rotate(emitter.direction, angle + spread);
+            emit(
+                &mut pool,
+                &mut particles,
+                emitter.kind,
+                origin,
+                body_velocity + direction * speed,
+                &mut rng,
+            );
+        }
+    }
+
+    for burst in bursts.iter() {
+        for _ in 0..burst.count {
+            let velocity = burst.velocity
+                + from_polar(
+                    burst.speed * rng.gen_range(0.2..1.0),
+                    rng.gen_range(0.0..std::f32::consts::TAU),
+                );
+            emit(
+                &mut pool,
+                &mut particles,
+                burst.kind,
+                burst.position,
+                velocity,
+                &mut rng,
+            );
+        }
+    }
+}
+
+/// Move live particles, shrink them as they age, and return expired ones to the pool.
+pub fn update_particles(
+    time: Res<Time>,
+    mut pool: ResMut<ParticlePool>,
+    mut particles: Query<(Entity, &mut Particle, &mut Transform, &mut Visible)>,
+) {
+    let dt = time.delta_seconds();
+    for (entity, mut particle, mut transform, mut visible) in particles.iter_mut() {
+        if !visible.is_visible {
+            continue;
+        }
+        particle.age += dt;
+        if particle.age >= particle.lifetime {
+            visible.is_visible = false;
+            pool.free.entry(particle.kind).or_default().push(entity);
+            continue;
+        }
+        transform.translation += (particle.velocity * dt).extend(0.0);
+        transform.scale = Vec3::splat(1.0 - particle.age / particle.lifetime);
+    }
+}
+
+pub fn impact_effects(
+    mut impacts: EventReader<ImpactEvent>,
+    mut bursts: EventWriter<ParticleBurst>,
+) {
+    for impact in impacts.iter() {
+        let count = (impact.energy.sqrt() / 50.0).clamp(2.0, 12.0) as usize;
+        bursts.send(ParticleBurst {
+            kind: ParticleKind::Spark,
+            position: impact.position,
+            velocity: Vec2::ZERO,
+            count,
+            speed: 150.0,
+        });
+    }
+}
+
+pub fn destruction_effects(
+    mut destroyed: EventReader<DestroyedEvent>,
+    mut bursts: EventWriter<ParticleBurst>,
+) {
+    for event in destroyed.iter() {
+        let mut burst = |kind, count, speed| {
+            bursts.send(ParticleBurst {
+                kind,
+                position: event.position,
+                velocity: event.velocity,
+                count,
+                speed,
+            })
+        };
+        match event.category {
+            Category::Asteroid => burst(ParticleKind::Debris, 12, 60.0),
+            Category::Ship | Category::Enemy => {
+                burst(ParticleKind::Explosion, 32, 150.0);
+                burst(ParticleKind::Debris, 8, 80.0);
+            }
+            _ => (),
+        }
+    }
+}
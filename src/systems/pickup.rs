@@ -0,0 +1,209 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng};
+
+use crate::{
+    components::{
+        damage::Category,
+        difficulty::Difficulty,
+        economy::CargoHold,
+        events::DestroyedEvent,
+        pickup::{Credits, Pickup, PickupKind, PowerUps},
+        ship::{Fuel, Weapon},
+        types::{MaxSturdiness, Player, SelectedHull, Sturdiness},
+    },
+    config::{GameConfig, HullCatalogue},
+    entities::pickup::PickupBundle,
+    util::from_polar,
+};
+
+/// Roll the loot table of each destroyed asteroid and enemy, and scatter whatever it drops.
+/// Loot gets more plentiful and generous as the difficulty rises.
+pub fn drop_loot(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    difficulty: Res<Difficulty>,
+    mut destroyed: EventReader<DestroyedEvent>,
+) {
+    let mut rng = rand::thread_rng();
+    let now = time.seconds_since_startup();
+    let elapsed = (now - difficulty.run_started) as f32;
+    for event in destroyed.iter().filter(|event| !event.fragment) {
+        let table = match event.category {
+            Category::Asteroid => &config.loot.asteroid,
+            Category::Enemy => &config.loot.enemy,
+            _ => continue,
+        };
+        let level = config.difficulty.level(event.position.length(), elapsed);
+        let quality = config
+            .difficulty
+            .scaling(difficulty.preset, level)
+            .loot_quality;
+        if !rng.gen_bool((table.chance * quality as f64).min(1.0)) {
+            continue;
+        }
+        let index = match WeightedIndex::new(table.drops.iter().map(|drop| drop.weight)) {
+            Ok(index) => index.sample(&mut rng),
+            Err(_) => continue,
+        };
+        let drop = &table.drops[index];
+        let scatter = from_polar(
+            rng.gen_range(20.0..60.0),
+            rng.gen_range(0.0..std::f32::consts::TAU),
+        );
+        commands.spawn_bundle(PickupBundle::new(
+            event.position,
+            event.velocity * 0.5 + scatter,
+            drop.kind,
+            drop.amount * quality,
+            now,
+            config.loot.lifetime,
+        ));
+    }
+}
+
+/// Pull pickups near the player towards the ship, except ore when the hold is full.
+pub fn magnetize_pickups(
+    config: Res<GameConfig>,
+    player: Query<(&RigidBodyPosition, &CargoHold), With<Player>>,
+    mut pickups: Query<(&Pickup, &RigidBodyPosition, &mut RigidBodyVelocity)>,
+) {
+    let (player, hold_full) = match player.single() {
+        Ok((pos, cargo)) => {
+            let position: Vec2 = pos.position.translation.into();
+            (position, cargo.is_full())
+        }
+        Err(_) => return,
+    };
+    let radius = config.loot.magnet_radius;
+    for (pickup, pos, mut vel) in pickups.iter_mut() {
+        // Ore with nowhere to go would only bump against the hull.
+        if hold_full && matches!(pickup.kind, PickupKind::Ore(_)) {
+            continue;
+        }
+        let position: Vec2 = pos.position.translation.into();
+        let offset = player - position;
+        let distance = offset.length();
+        if distance < radius && distance > 0.0 {
+            vel.linvel = (offset / distance * config.loot.magnet_speed).into();
+        }
+    }
+}
+
+/// Apply pickups the player's ship touches, and remove them.
+pub fn collect_pickups(
+    mut commands: Commands,
+    mut intersections: EventReader<IntersectionEvent>,
+    narrow_phase: Res<NarrowPhase>,
+    config: Res<GameConfig>,
+    hulls: Res<HullCatalogue>,
+    selected: Res<SelectedHull>,
+    mut credits: ResMut<Credits>,
+    mut pickups: Query<&mut Pickup>,
+    mut player: Query<
+        (
+            Entity,
+            &mut Fuel,
+            &mut Sturdiness,
+            &MaxSturdiness,
+            &mut Weapon,
+            &mut PowerUps,
+            &mut CargoHold,
+        ),
+        With<Player>,
+    >,
+) {
+    let mut touching = intersections
+        .iter()
+        .filter(|event| event.intersecting)
+        .map(|event| (event.collider1.entity(), event.collider2.entity()))
+        .collect::<Vec<_>>();
+    // Intersections are only reported when they start, so ore left floating against a full hold
+    // is picked up from whatever still touches the ship once room is made for it.
+    for (entity, .., cargo) in player.iter_mut() {
+        if cargo.is_changed() && !cargo.is_full() {
+            touching.extend(
+                narrow_phase
+                    .intersections_with(entity.handle())
+                    .filter(|(_, _, intersecting)| *intersecting)
+                    .map(|(a, b, _)| (a.entity(), b.entity())),
+            );
+        }
+    }
+
+    let mut collected = vec![];
+    for (a, b) in touching {
+        let (entity, other) = if pickups.get_mut(a).is_ok() {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        if collected.contains(&entity) {
+            continue;
+        }
+        let mut pickup = match pickups.get_mut(entity) {
+            Ok(pickup) => pickup,
+            Err(_) => continue,
+        };
+        // Other ships can touch pickups too, but only the player's collects them.
+        let (_, mut fuel, mut sturdiness, max, mut weapon, mut power_ups, mut cargo) =
+            match player.get_mut(other) {
+                Ok(player) => player,
+                Err(_) => continue,
+            };
+        match pickup.kind {
+            PickupKind::Fuel => {
+                let capacity = hulls
+                    .hulls
+                    .get(selected.0)
+                    .map_or(f32::INFINITY, |hull| hull.ship.fuel);
+                fuel.0 = (fuel.0 + pickup.amount).min(capacity);
+            }
+            PickupKind::Repair => {
+                sturdiness.0 = (sturdiness.0 + pickup.amount).min(max.0);
+            }
+            PickupKind::Ammo => {
+                let shots = (pickup.amount * config.loot.shots_per_ammo).round() as u32;
+                match weapon.ammo {
+                    Some(ammo) => weapon.ammo = Some(ammo + shots),
+                    None => {
+                        *weapon = Weapon {
+                            name: "Piercer".to_string(),
+                            ammo: Some(shots),
+                            piercing: config.loot.ammo_piercing,
+                        }
+                    }
+                }
+            }
+            PickupKind::Credits => credits.0 += pickup.amount.round() as u32,
+            PickupKind::PowerUp(power_up) => {
+                // Picking up a power-up that's already running tops up its time.
+                *power_ups.0.entry(power_up).or_insert(0.0) += pickup.amount;
+            }
+            PickupKind::Ore(ore) => {
+                pickup.amount -= cargo.add(ore, pickup.amount);
+                // Whatever doesn't fit in the hold is left floating.
+                if pickup.amount > 0.0 {
+                    continue;
+                }
+            }
+        }
+        collected.push(entity);
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Count down active power-ups and remove the ones that ran out.
+pub fn tick_power_ups(time: Res<Time>, mut ships: Query<&mut PowerUps>) {
+    let dt = time.delta_seconds();
+    for mut power_ups in ships.iter_mut() {
+        if power_ups.0.is_empty() {
+            continue;
+        }
+        power_ups.0.retain(|_, remaining| {
+            *remaining -= dt;
+            *remaining > 0.0
+        });
+    }
+}
@@ -1,14 +1,78 @@
-use bevy::{
-    input::touch::TouchPhase,
-    prelude::*,
-    render::{camera::Camera, render_graph::base::camera},
-};
+use bevy::{input::touch::TouchPhase, prelude::*};
+
+use bevy_rapier2d::prelude::*;
 
-use crate::components::{
-    ship::*,
-    types::{Player, Score, ScoreText},
+use crate::{
+    components::{
+        boss::BossEncounters,
+        console::*,
+        damage::Category,
+        difficulty::Difficulty,
+        economy::{CargoHold, Upgrades},
+        hud::Lives,
+        pickup::{Credits, PowerUps},
+        shield::Shield,
+        ship::*,
+        station::Docking,
+        types::{AppState, Player, Score, SelectedHull},
+    },
+    config::{GameConfig, Hull, HullCatalogue},
+    entities::{entity::ShapeError, ship::Ship},
+    util::outline_radius,
 };
 
+/// Where each run starts.
+pub const PLAYER_START: [f32; 2] = [0.0, -215.0];
+
+/// Spawn the player's ship with the given hull.
+pub fn spawn_player_ship(
+    commands: &mut Commands,
+    hull: &Hull,
+    position: Vec2,
+) -> Result<(), ShapeError> {
+    let ship = Ship::new(position.into(), hull, Category::Ship)?;
+    let mut player = commands.spawn_bundle(ship);
+    player
+        .insert(Player {})
+        .insert(PowerUps::default())
+        .insert(CargoHold::new(hull.ship.cargo))
+        .insert(Upgrades::default());
+    if let Some(shield) = &hull.shield {
+        player.insert(Shield::new(shield, outline_radius(&hull.shape) + 6.0));
+    }
+    Ok(())
+}
+
+/// Start a run with the selected hull.
+pub fn spawn_player(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    hulls: Res<HullCatalogue>,
+    selected: Res<SelectedHull>,
+    mut score: ResMut<Score>,
+    mut lives: ResMut<Lives>,
+    mut difficulty: ResMut<Difficulty>,
+    mut credits: ResMut<Credits>,
+    mut docking: ResMut<Docking>,
+    mut encounters: ResMut<BossEncounters>,
+    mut state: ResMut<State<AppState>>,
+) {
+    score.0 = 0;
+    credits.0 = 0;
+    *docking = Docking::default();
+    *encounters = BossEncounters::default();
+    lives.0 = config.player.lives;
+    difficulty.run_started = time.seconds_since_startup();
+    if let Some(hull) = hulls.hulls.get(selected.0) {
+        if let Err(err) = spawn_player_ship(&mut commands, hull, PLAYER_START.into()) {
+            error!("Could not spawn the {} hull: {}", hull.name, err);
+            // Go back to picking a hull, rather than leave a run going with no ship in it.
+            let _ = state.set(AppState::HullSelect);
+        }
+    }
+}
+
 pub fn mock_touch(
     mouse: Res<Input<MouseButton>>,
     windows: Res<Windows>,
@@ -26,7 +90,7 @@ pub fn mock_touch(
     };
     if let (Some(phase), Some(cursor_pos)) = (touch_phase, window.cursor_position()) {
         touch_events.send(TouchInput {
-            phase: phase,
+            phase,
             position: cursor_pos,
             force: None,
             id: 0,
@@ -34,47 +98,57 @@ pub fn mock_touch(
     }
 }
 
-const DEBOUNCE_DIST: f32 = 10.0;
-
 pub fn player(
     keyboard_input: Res<Input<KeyCode>>,
     touch_input: Res<Touches>,
     time: Res<Time>,
+    config: Res<GameConfig>,
+    console: Res<Console>,
+    state: Res<State<AppState>>,
     mut query: Query<&mut Controls, With<Player>>,
     windows: Res<Windows>,
 ) {
     let window = windows.get_primary().unwrap();
     let width = window.width();
+    let debounce_dist = config.controls.debounce_dist;
+    let cooldown = config.weapon.cooldown;
 
     if let Ok(mut controls) = query.single_mut() {
         controls.last_shot += time.delta_seconds();
+        // Typing into the console or using a station menu shouldn't fly the ship.
+        if console.open || *state.current() == AppState::Docked {
+            controls.thrust = false;
+            controls.rotate = None;
+            controls.shoot = false;
+            return;
+        }
         controls.rotate = if keyboard_input.pressed(KeyCode::Left) {
-            Some(RotationDir::LEFT)
+            Some(RotationDir::Left)
         } else if keyboard_input.pressed(KeyCode::Right) {
-            Some(RotationDir::RIGHT)
+            Some(RotationDir::Right)
         } else {
             None
         };
 
         controls.thrust = keyboard_input.pressed(KeyCode::Up);
-        controls.shoot = keyboard_input.pressed(KeyCode::Space) && controls.last_shot >= 0.25;
+        controls.shoot = keyboard_input.pressed(KeyCode::Space) && controls.last_shot >= cooldown;
 
         // If any touch input is received, override keyboard.
         for finger in touch_input.iter() {
             if finger.position().x < width * 0.5 {
                 let dist = finger.distance();
-                if dist.y > DEBOUNCE_DIST {
+                if dist.y > debounce_dist {
                     controls.thrust = true;
                 }
-                controls.rotate = if dist.x < -DEBOUNCE_DIST {
-                    Some(RotationDir::LEFT)
-                } else if dist.x > DEBOUNCE_DIST {
-                    Some(RotationDir::RIGHT)
+                controls.rotate = if dist.x < -debounce_dist {
+                    Some(RotationDir::Left)
+                } else if dist.x > debounce_dist {
+                    Some(RotationDir::Right)
                 } else {
                     None
                 };
             } else {
-                controls.shoot = controls.last_shot >= 0.25;
+                controls.shoot = controls.last_shot >= cooldown;
             }
         }
 
@@ -84,34 +158,62 @@ pub fn player(
     }
 }
 
-const TRAIL_DIST: f32 = 200.0;
+pub fn score_command(world: &mut World, args: &[&str]) -> CommandResult {
+    let score: i32 = parse_arg(args, 0, "score")?;
+    world.get_resource_mut::<Score>().unwrap().0 = score;
+    Ok(format!("score set to {}", score))
+}
 
-pub fn camera_tracking(
-    mut camera: Query<(&mut Transform, &Camera), Without<Player>>,
-    player: Query<&Transform, (With<Player>, Without<Camera>)>,
-) {
-    if let Ok(player_pos) = player.single() {
-        for (mut cam_pos, cam) in camera.iter_mut() {
-            // Only move the entity camera, not the UI camera.
-            if cam.name == Some(camera::CAMERA_2D.to_string()) {
-                // Project to 2 dimensions.
-                let player_pos2 = Vec2::new(player_pos.translation.x, player_pos.translation.y);
-                let cam_pos2 = Vec2::new(cam_pos.translation.x, cam_pos.translation.y);
-                let dist = player_pos2.distance(cam_pos2);
-                if dist > TRAIL_DIST {
-                    let new_pos =
-                        (cam_pos2 - player_pos2).normalize() * (TRAIL_DIST - dist) + cam_pos2;
-
-                    cam_pos.translation.x = new_pos.x;
-                    cam_pos.translation.y = new_pos.y;
-                }
-            }
-        }
+pub fn fuel_command(world: &mut World, _args: &[&str]) -> CommandResult {
+    let selected = world.get_resource::<SelectedHull>().unwrap().0;
+    let capacity = match world
+        .get_resource::<HullCatalogue>()
+        .unwrap()
+        .hulls
+        .get(selected)
+    {
+        Some(hull) => hull.ship.fuel,
+        None => return Err("no hull selected".to_string()),
+    };
+    let mut fuel = world.query_filtered::<&mut Fuel, With<Player>>();
+    for mut fuel in fuel.iter_mut(world) {
+        fuel.0 = capacity;
     }
+    Ok("fuel refilled".to_string())
+}
+
+pub fn god_command(world: &mut World, _args: &[&str]) -> CommandResult {
+    let mut god_mode = world.get_resource_mut::<GodMode>().unwrap();
+    god_mode.0 = !god_mode.0;
+    Ok(format!(
+        "god mode {}",
+        if god_mode.0 { "enabled" } else { "disabled" }
+    ))
+}
+
+/// Move the player to the center of the chunk with the given chunk coordinates.
+pub fn teleport_command(world: &mut World, args: &[&str]) -> CommandResult {
+    let x: i32 = parse_arg(args, 0, "x")?;
+    let y: i32 = parse_arg(args, 1, "y")?;
+    let size = world.get_resource::<GameConfig>().unwrap().world.chunk_size;
+    let target = (Vec2::new(x as f32, y as f32) + Vec2::splat(0.5)) * size;
+    move_player(world, target);
+    Ok(format!("teleported to chunk ({}, {})", x, y))
+}
+
+/// Move the player to the given world coordinates.
+pub fn goto_command(world: &mut World, args: &[&str]) -> CommandResult {
+    let x: f32 = parse_arg(args, 0, "x")?;
+    let y: f32 = parse_arg(args, 1, "y")?;
+    let target = Vec2::new(x, y);
+    move_player(world, target);
+    Ok(format!("moved to ({:.0}, {:.0})", target.x, target.y))
 }
 
-pub fn display_score(score: Res<Score>, mut text: Query<&mut Text, With<ScoreText>>) {
-    if let Ok(mut text) = text.single_mut() {
-        text.sections[0].value = format!("Score: {}", score.0);
+fn move_player(world: &mut World, target: Vec2) {
+    let mut player = world.query_filtered::<&mut RigidBodyPosition, With<Player>>();
+    for mut pos in player.iter_mut(world) {
+        pos.position.translation.vector = target.into();
+        pos.next_position.translation.vector = target.into();
     }
 }
@@ -0,0 +1,244 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::{
+        console::Console,
+        damage::Category,
+        difficulty::Difficulty,
+        events::DestroyedEvent,
+        hud::Lives,
+        pickup::{PowerUp, PowerUps},
+        station::Docking,
+        types::{AppState, HullSelectText, Player, Score, SelectedHull, Sturdiness},
+    },
+    config::{GameConfig, HullCatalogue},
+    progression::Progression,
+    systems::player::{spawn_player_ship, PLAYER_START},
+};
+
+pub fn spawn_hull_select(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    hulls: Res<HullCatalogue>,
+    progression: Res<Progression>,
+    mut selected: ResMut<SelectedHull>,
+) {
+    // The catalogue may have changed since the last run, so fall back to the first unlocked hull.
+    let usable = hulls
+        .hulls
+        .get(selected.0)
+        .is_some_and(|hull| progression.is_unlocked(hull));
+    if !usable {
+        selected.0 = hulls
+            .hulls
+            .iter()
+            .position(|hull| progression.is_unlocked(hull))
+            .unwrap_or(0);
+    }
+
+    commands
+        .spawn_bundle(TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Percent(35.0),
+                    left: Val::Percent(30.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            text: Text::with_section(
+                "",
+                TextStyle {
+                    font_size: 24.0,
+                    color: Color::WHITE,
+                    font: asset_server.load("FiraSans-Bold.ttf"),
+                },
+                Default::default(),
+            ),
+            ..Default::default()
+        })
+        .insert(HullSelectText {});
+}
+
+/// Start on the difficulty preset from the config.
+pub fn apply_default_difficulty(config: Res<GameConfig>, mut difficulty: ResMut<Difficulty>) {
+    difficulty.preset = config.difficulty.preset;
+}
+
+/// Cycle through the catalogue with left and right, pick the difficulty with up and down,
+/// and launch with return.
+/// Locked hulls are shown with what it takes to unlock them, but can't be launched.
+pub fn hull_select(
+    keyboard_input: Res<Input<KeyCode>>,
+    console: Res<Console>,
+    hulls: Res<HullCatalogue>,
+    progression: Res<Progression>,
+    mut selected: ResMut<SelectedHull>,
+    mut difficulty: ResMut<Difficulty>,
+    mut state: ResMut<State<AppState>>,
+    mut text: Query<&mut Text, With<HullSelectText>>,
+) {
+    let count = hulls.hulls.len();
+    if count == 0 {
+        return;
+    }
+    if !console.open {
+        if keyboard_input.just_pressed(KeyCode::Left) {
+            selected.0 = (selected.0 + count - 1) % count;
+        } else if keyboard_input.just_pressed(KeyCode::Right) {
+            selected.0 = (selected.0 + 1) % count;
+        }
+        if keyboard_input.just_pressed(KeyCode::Up) {
+            difficulty.preset = difficulty.preset.harder();
+        } else if keyboard_input.just_pressed(KeyCode::Down) {
+            difficulty.preset = difficulty.preset.easier();
+        }
+    }
+    let hull = &hulls.hulls[selected.0.min(count - 1)];
+    let unlocked = progression.is_unlocked(hull);
+
+    if let Ok(mut text) = text.single_mut() {
+        text.sections[0].value = format!(
+            "Choose your hull ({}/{})\n\n< {} >\nhull {:.0}  mass {:.0}  thrust {:.0}  spin {:.1}\nfuel {:.0}  cargo {:.0}  guns {}\n\ndifficulty: {} (up/down to change)\n{}\nbest score: {}",
+            selected.0 + 1,
+            count,
+            hull.name,
+            hull.ship.sturdiness,
+            hull.mass,
+            hull.ship.thrust,
+            hull.ship.spin,
+            hull.ship.fuel,
+            hull.ship.cargo,
+            hull.hardpoints.len(),
+            difficulty.preset.name(),
+            if unlocked {
+                "press enter to launch".to_string()
+            } else {
+                format!("locked: reach a score of {} to unlock", hull.unlock_score)
+            },
+            progression.best_score,
+        );
+    }
+
+    if unlocked && !console.open && keyboard_input.just_pressed(KeyCode::Return) {
+        let _ = state.set(AppState::InGame);
+    }
+}
+
+pub fn despawn_hull_select(mut commands: Commands, text: Query<Entity, With<HullSelectText>>) {
+    for entity in text.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn award_score(
+    mut destroyed: EventReader<DestroyedEvent>,
+    mut score: ResMut<Score>,
+    player: Query<&PowerUps, With<Player>>,
+) {
+    let multiplier = match player.single() {
+        Ok(power_ups) if power_ups.is_active(PowerUp::DoubleScore) => 2,
+        _ => 1,
+    };
+    for event in destroyed.iter().filter(|event| !event.fragment) {
+        score.0 += multiplier
+            * match event.category {
+                Category::Asteroid => 1,
+                Category::Enemy => 5,
+                _ => 0,
+            };
+    }
+}
+
+/// When the player's ship is destroyed, fly the next one if there are lives left,
+/// from the last station docked at. Otherwise record the run and go back to hull selection.
+pub fn end_run(
+    mut commands: Commands,
+    mut destroyed: EventReader<DestroyedEvent>,
+    score: Res<Score>,
+    hulls: Res<HullCatalogue>,
+    selected: Res<SelectedHull>,
+    docking: Res<Docking>,
+    mut lives: ResMut<Lives>,
+    mut progression: ResMut<Progression>,
+    mut state: ResMut<State<AppState>>,
+    player: Query<&Sturdiness, With<Player>>,
+) {
+    // Other ships can fly on the player's side, so check it was the player's that went.
+    let ship_lost = destroyed
+        .iter()
+        .any(|event| event.category == Category::Ship);
+    if ship_lost && !player.iter().any(|sturdiness| sturdiness.0 > 0.0) {
+        lives.0 = lives.0.saturating_sub(1);
+        if lives.0 > 0 {
+            if let Some(hull) = hulls.hulls.get(selected.0) {
+                let position = docking.checkpoint.unwrap_or_else(|| PLAYER_START.into());
+                match spawn_player_ship(&mut commands, hull, position) {
+                    Ok(()) => return,
+                    Err(err) => error!("Could not spawn the {} hull: {}", hull.name, err),
+                }
+            }
+        }
+        if progression.record_run(score.0) {
+            info!("New best score: {}", score.0);
+        }
+        progression.save();
+        let _ = state.set(AppState::HullSelect);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::Events;
+
+    use super::*;
+
+    fn destroyed(category: Category, fragment: bool) -> DestroyedEvent {
+        DestroyedEvent {
+            entity: Entity::new(0),
+            category,
+            position: Vec2::ZERO,
+            rotation: 0.0,
+            velocity: Vec2::ZERO,
+            angular_velocity: 0.0,
+            mass: 1.0,
+            material: None,
+            outline: Vec::new(),
+            impact: None,
+            fragment,
+        }
+    }
+
+    fn score_for(events: Vec<DestroyedEvent>) -> i32 {
+        let mut world = World::default();
+        world.insert_resource(Score::default());
+        let mut queue = Events::<DestroyedEvent>::default();
+        for event in events {
+            queue.send(event);
+        }
+        world.insert_resource(queue);
+        SystemStage::single(award_score.system()).run(&mut world);
+        world.get_resource::<Score>().unwrap().0
+    }
+
+    #[test]
+    fn whole_asteroids_and_enemies_score() {
+        let score = score_for(vec![
+            destroyed(Category::Asteroid, false),
+            destroyed(Category::Enemy, false),
+            destroyed(Category::Bullet, false),
+        ]);
+        assert_eq!(score, 6);
+    }
+
+    #[test]
+    fn fragments_score_nothing() {
+        let score = score_for(vec![
+            destroyed(Category::Asteroid, false),
+            destroyed(Category::Asteroid, true),
+            destroyed(Category::Asteroid, true),
+        ]);
+        assert_eq!(score, 1);
+    }
+}
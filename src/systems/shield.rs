@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+
+use crate::components::shield::*;
+use crate::entities::shield::{build_bubble, ShieldBubbleBundle};
+
+/// Give newly shielded ships a bubble to show the shield's strength.
+pub fn attach_shield_bubbles(
+    mut commands: Commands,
+    query: Query<(Entity, &Shield), Added<Shield>>,
+) {
+    for (entity, shield) in query.iter() {
+        commands.entity(entity).with_children(|parent| {
+            parent.spawn_bundle(ShieldBubbleBundle::new(shield.radius));
+        });
+    }
+}
+
+pub fn recharge_shields(time: Res<Time>, mut query: Query<&mut Shield>) {
+    let dt = time.delta_seconds();
+    for mut shield in query.iter_mut() {
+        shield.since_hit += dt;
+        if shield.since_hit >= shield.recharge_delay && shield.strength < shield.capacity {
+            shield.strength = (shield.strength + shield.recharge_rate * dt).min(shield.capacity);
+        }
+    }
+}
+
+/// Redraw shield bubbles whose strength has moved to a different opacity level.
+pub fn shield_visuals(
+    mut commands: Commands,
+    shields: Query<&Shield>,
+    mut bubbles: Query<(Entity, &Parent, &mut ShieldBubble)>,
+) {
+    for (entity, parent, mut bubble) in bubbles.iter_mut() {
+        if let Ok(shield) = shields.get(parent.0) {
+            let step = shield.step();
+            if step != bubble.step {
+                bubble.step = step;
+                commands
+                    .entity(entity)
+                    .insert_bundle(build_bubble(shield.radius, step));
+            }
+        }
+    }
+}
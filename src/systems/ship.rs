@@ -1,71 +1,165 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
+use rand::Rng;
 
-use crate::components::ship::*;
-use crate::entities::{bullet::BulletBundle, entity::build_geometry, ship::EXHAUST_SHAPE};
+use crate::components::{
+    damage::*,
+    particle::ParticleEmitter,
+    pickup::{PowerUp, PowerUps},
+    ship::*,
+    types::AppState,
+};
+use crate::config::{GameConfig, Shapes};
+use crate::entities::{bullet::BulletBundle, ship::ThrusterBundle};
 
 pub fn weapons(
     mut commands: Commands,
     time: Res<Time>,
-    mut query: Query<(&Controls, &RigidBodyPosition, &RigidBodyVelocity)>,
+    state: Res<State<AppState>>,
+    config: Res<GameConfig>,
+    shapes: Res<Shapes>,
+    mut query: Query<(
+        Entity,
+        &Category,
+        &Controls,
+        &mut Weapon,
+        &Hardpoints,
+        &RigidBodyPosition,
+        &RigidBodyVelocity,
+    )>,
 ) {
-    for (controls, pos, vel) in query.iter_mut() {
-        if controls.shoot {
-            commands.spawn_bundle(BulletBundle::launch_from(
+    // The world is paused while docked, so nobody gets to shoot into it.
+    if *state.current() == AppState::Docked {
+        return;
+    }
+    for (entity, category, controls, mut weapon, hardpoints, pos, vel) in query.iter_mut() {
+        if !controls.shoot {
+            continue;
+        }
+        match weapon.ammo {
+            Some(0) => continue,
+            Some(ammo) => weapon.ammo = Some(ammo - 1),
+            None => (),
+        }
+        for hardpoint in hardpoints.0.iter() {
+            match BulletBundle::launch_from(
                 pos,
                 vel,
+                *hardpoint,
                 time.seconds_since_startup(),
-            ));
+                Owner {
+                    entity,
+                    category: *category,
+                },
+                &config.weapon,
+                &shapes,
+            ) {
+                Ok(bullet) => {
+                    commands.spawn_bundle(bullet.piercing(weapon.piercing));
+                }
+                Err(err) => warn!("Could not launch bullet: {}", err),
+            }
+        }
+        // Limited weapons are swapped back for the standard one once they run dry.
+        if weapon.ammo == Some(0) {
+            *weapon = Weapon::default();
         }
     }
 }
 
-pub fn impulse(
+/// Give newly spawned ships their thruster flames. They stay attached, hidden while idle.
+pub fn attach_thrusters(
     mut commands: Commands,
+    shapes: Res<Shapes>,
+    query: Query<(Entity, &ThrusterMounts), Added<ThrusterMounts>>,
+) {
+    for (entity, mounts) in query.iter() {
+        commands.entity(entity).with_children(|parent| {
+            for mount in mounts.0.iter() {
+                parent.spawn_bundle(ThrusterBundle::new(mount, &shapes.exhaust));
+            }
+        });
+    }
+}
+
+pub fn impulse(
     mut query: Query<(
-        Entity,
         &Controls,
         &Engines,
         &RigidBodyPosition,
         &mut RigidBodyVelocity,
         &mut RigidBodyForces,
-        Option<&Children>,
+        &mut ParticleEmitter,
+        Option<&PowerUps>,
     )>,
-    thruster_query: Query<Entity, With<Thruster>>,
 ) {
-    for (entity, controls, engines, pos, mut vel, mut forces, children) in query.iter_mut() {
+    /// Thrust multiplier while the overdrive power-up is active.
+    const OVERDRIVE_THRUST: f32 = 1.5;
+
+    for (controls, engines, pos, mut vel, mut forces, mut exhaust, power_ups) in query.iter_mut() {
+        exhaust.active = controls.thrust;
+
         vel.angvel = match controls.rotate {
-            Some(RotationDir::LEFT) => engines.spin,
-            Some(RotationDir::RIGHT) => -engines.spin,
+            Some(RotationDir::Left) => engines.spin,
+            Some(RotationDir::Right) => -engines.spin,
             None => 0.0,
         };
 
-        match children.map_or(None, |c| {
-            c.iter()
-                .find(|child| thruster_query.get(*child.clone()).is_ok())
-        }) {
-            Some(thruster) => {
-                if !controls.thrust {
-                    commands.entity(thruster.clone()).despawn()
-                }
-            }
-            None => {
-                if controls.thrust {
-                    let thruster = commands
-                        .spawn_bundle(build_geometry(&EXHAUST_SHAPE))
-                        .insert(Thruster {})
-                        .id();
-                    commands.entity(entity).push_children(&[thruster]);
-                }
-            }
+        let overdrive = match power_ups {
+            Some(power_ups) if power_ups.is_active(PowerUp::Overdrive) => OVERDRIVE_THRUST,
+            _ => 1.0,
         };
-
         forces.force = (if controls.thrust {
             let angle = pos.position.rotation.angle();
             Vec2::new(-angle.sin(), angle.cos())
         } else {
             Vec2::default()
-        } * engines.thrust)
+        } * engines.thrust
+            * overdrive)
             .into();
     }
 }
+
+/// Ramp each thruster towards its ship's controls, and stretch its flame by the current level.
+pub fn animate_thrusters(
+    time: Res<Time>,
+    ships: Query<&Controls>,
+    mut thrusters: Query<(&Parent, &mut Thruster, &mut Transform, &mut Visible)>,
+) {
+    const IGNITION_RATE: f32 = 8.0;
+    const CUTOFF_RATE: f32 = 12.0;
+
+    let mut rng = rand::thread_rng();
+    let dt = time.delta_seconds();
+    for (parent, mut thruster, mut transform, mut visible) in thrusters.iter_mut() {
+        let controls = match ships.get(parent.0) {
+            Ok(controls) => controls,
+            Err(_) => continue,
+        };
+        let firing = match (thruster.mount.kind, &controls.rotate) {
+            (ThrusterKind::Main, _) => controls.thrust,
+            (ThrusterKind::RotateLeft, Some(RotationDir::Left)) => true,
+            (ThrusterKind::RotateRight, Some(RotationDir::Right)) => true,
+            _ => false,
+        };
+        thruster.level = if firing {
+            (thruster.level + IGNITION_RATE * dt).min(1.0)
+        } else {
+            (thruster.level - CUTOFF_RATE * dt).max(0.0)
+        };
+
+        let lit = thruster.level > 0.0;
+        if visible.is_visible != lit {
+            visible.is_visible = lit;
+        }
+        if lit {
+            let flicker = rng.gen_range(0.75..1.15);
+            let scale = thruster.mount.scale;
+            transform.scale = Vec3::new(
+                scale * (0.8 + 0.2 * thruster.level),
+                scale * thruster.level * flicker,
+                1.0,
+            );
+        }
+    }
+}
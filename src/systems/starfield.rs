@@ -0,0 +1,117 @@
+use bevy::{prelude::*, render::camera::OrthographicProjection};
+
+use crate::{
+    components::{
+        camera::CameraController,
+        chunk::WorldSeed,
+        starfield::{BackgroundLayer, LayerContents},
+    },
+    config::GameConfig,
+    entities::starfield::build_layer_chunk,
+    util::hash_coords,
+};
+
+/// Depth of the farthest background layer. Nearer layers are stacked in front of it.
+const BACKGROUND_Z: f32 = -10.0;
+
+/// Spawn an empty entity per background layer. Their chunks are filled in by `update_background`.
+pub fn spawn_background_layers(mut commands: Commands, config: Res<GameConfig>) {
+    let nebula = &config.starfield.nebula;
+    let stars = config
+        .starfield
+        .layers
+        .iter()
+        .map(|layer| (layer.parallax, LayerContents::Stars(layer.clone())));
+    let mut layers = std::iter::once((nebula.parallax, LayerContents::Nebula(nebula.clone())))
+        .chain(stars)
+        .collect::<Vec<_>>();
+    // Layers that move less are farther away, so draw them first.
+    layers.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+
+    for (index, (parallax, contents)) in layers.into_iter().enumerate() {
+        commands.spawn_bundle((
+            Transform::from_xyz(0.0, 0.0, BACKGROUND_Z + index as f32 * 0.1),
+            GlobalTransform::default(),
+            BackgroundLayer {
+                index: index as u64,
+                parallax,
+                contents,
+                chunks: Default::default(),
+            },
+        ));
+    }
+}
+
+/// Move background layers with the camera, scaled by their parallax,
+/// and keep the chunks covering the screen spawned.
+/// Chunk contents only depend on the world seed and their coordinates, so they look
+/// the same every time they are revisited.
+pub fn update_background(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    seed: Res<WorldSeed>,
+    windows: Res<Windows>,
+    camera: Query<(&Transform, &OrthographicProjection), With<CameraController>>,
+    mut layers: Query<(Entity, &mut BackgroundLayer, &mut Transform), Without<CameraController>>,
+) {
+    let (camera_transform, projection) = match camera.single() {
+        Ok(camera) => camera,
+        Err(_) => return,
+    };
+    let window = match windows.get_primary() {
+        Some(window) => window,
+        None => return,
+    };
+    let size = config.world.chunk_size;
+    let camera_pos = camera_transform.translation.truncate();
+    let half_extent = Vec2::new(window.width(), window.height()) / 2.0 * projection.scale;
+
+    for (entity, mut layer, mut transform) in layers.iter_mut() {
+        if seed.is_changed() {
+            for chunk in layer.chunks.drain().filter_map(|(_, chunk)| chunk) {
+                commands.entity(chunk).despawn();
+            }
+        }
+
+        let offset = camera_pos * layer.parallax;
+        transform.translation.x = offset.x;
+        transform.translation.y = offset.y;
+
+        // Part of the layer under the camera, in the layer's own space.
+        let center = camera_pos - offset;
+        let min = ((center - half_extent) / size).floor();
+        let max = ((center + half_extent) / size).floor();
+        let (min_x, min_y, max_x, max_y) = (min.x as i32, min.y as i32, max.x as i32, max.y as i32);
+        let in_view = |&(x, y): &(i32, i32)| x >= min_x && x <= max_x && y >= min_y && y <= max_y;
+
+        let stale = layer
+            .chunks
+            .keys()
+            .filter(|coords| !in_view(coords))
+            .copied()
+            .collect::<Vec<_>>();
+        for coords in stale {
+            if let Some(Some(chunk)) = layer.chunks.remove(&coords) {
+                commands.entity(chunk).despawn();
+            }
+        }
+
+        let layer_seed = seed.0.wrapping_add(layer.index);
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                if layer.chunks.contains_key(&(x, y)) {
+                    continue;
+                }
+                let chunk = build_layer_chunk(&layer.contents, size, hash_coords(layer_seed, x, y))
+                    .map(|mut bundle| {
+                        bundle.transform =
+                            Transform::from_xyz(x as f32 * size, y as f32 * size, 0.0);
+                        let chunk = commands.spawn_bundle(bundle).id();
+                        commands.entity(entity).push_children(&[chunk]);
+                        chunk
+                    });
+                layer.chunks.insert((x, y), chunk);
+            }
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    components::{
+        console::Console,
+        difficulty::Difficulty,
+        economy::{CargoHold, Ore, Upgrade, Upgrades},
+        hud::HudMaterials,
+        pickup::Credits,
+        ship::{Engines, Fuel},
+        station::{Docking, Station, StationMenu, StationMenuText},
+        types::{AppState, MaxSturdiness, Player, Score, SelectedHull, SpawnedAt, Sturdiness},
+    },
+    config::{EconomyConfig, GameConfig, HullCatalogue},
+    progression::Progression,
+};
+
+/// Dock when the player's ship settles into a station's docking port.
+/// Stations don't move, so matching their velocity means all but stopping.
+pub fn dock(
+    config: Res<GameConfig>,
+    mut docking: ResMut<Docking>,
+    mut state: ResMut<State<AppState>>,
+    stations: Query<(Entity, &Station)>,
+    player: Query<(&RigidBodyPosition, &RigidBodyVelocity), With<Player>>,
+) {
+    let (pos, vel) = match player.single() {
+        Ok(player) => player,
+        Err(_) => return,
+    };
+    let position: Vec2 = pos.position.translation.into();
+    let nearby = stations
+        .iter()
+        .find(|(_, station)| station.port.distance(position) < config.stations.dock_radius);
+    let (entity, station) = match nearby {
+        Some(nearby) => nearby,
+        None => {
+            docking.station = None;
+            return;
+        }
+    };
+    // Just undocked from this one, so wait until the ship has left the port.
+    if docking.station == Some(entity) {
+        return;
+    }
+    if Vec2::from(vel.linvel).length() > config.stations.dock_max_speed {
+        return;
+    }
+    docking.station = Some(entity);
+    docking.checkpoint = Some(station.port);
+    let _ = state.push(AppState::Docked);
+}
+
+/// Pause the world and open the station menu.
+pub fn enter_station(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    materials: Res<HudMaterials>,
+    mut docking: ResMut<Docking>,
+    mut rapier: ResMut<RapierConfiguration>,
+    mut player: Query<&mut RigidBodyVelocity, With<Player>>,
+) {
+    docking.docked_at = time.seconds_since_startup();
+    rapier.physics_pipeline_active = false;
+    if let Ok(mut vel) = player.single_mut() {
+        vel.linvel = Vec2::ZERO.into();
+        vel.angvel = 0.0;
+    }
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Percent(30.0),
+                    left: Val::Percent(30.0),
+                    ..Default::default()
+                },
+                padding: Rect::all(Val::Px(10.0)),
+                ..Default::default()
+            },
+            material: materials.panel.clone(),
+            ..Default::default()
+        })
+        .insert(StationMenu {})
+        .with_children(|menu| {
+            menu.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    "",
+                    TextStyle {
+                        font_size: 20.0,
+                        color: Color::TEAL,
+                        font: asset_server.load("FiraSans-Bold.ttf"),
+                    },
+                    Default::default(),
+                ),
+                ..Default::default()
+            })
+            .insert(StationMenuText {});
+        });
+}
+
+fn upgrade_price(economy: &EconomyConfig, upgrades: &Upgrades, upgrade: Upgrade) -> u32 {
+    economy.upgrade_price * (upgrades.level(upgrade) + 1)
+}
+
+/// Buy as much of `wanted` at `price` each as `credits` allows, and return how much that was.
+fn buy(credits: &mut Credits, wanted: f32, price: f32) -> f32 {
+    let wanted = wanted.max(0.0);
+    if price <= 0.0 {
+        return wanted;
+    }
+    let bought = wanted.min(credits.0 as f32 / price).floor();
+    // Rounding can put the cost a credit over what there is, so never charge more than that.
+    let cost = ((bought * price).ceil() as u32).min(credits.0);
+    credits.0 -= cost;
+    bought
+}
+
+/// Trade, repair, buy upgrades and save with the number keys, and undock with return or escape.
+pub fn station_menu(
+    keyboard_input: Res<Input<KeyCode>>,
+    console: Res<Console>,
+    config: Res<GameConfig>,
+    hulls: Res<HullCatalogue>,
+    selected: Res<SelectedHull>,
+    score: Res<Score>,
+    mut credits: ResMut<Credits>,
+    mut progression: ResMut<Progression>,
+    mut state: ResMut<State<AppState>>,
+    mut saved: Local<bool>,
+    mut player: Query<
+        (
+            &mut CargoHold,
+            &mut Fuel,
+            &mut Sturdiness,
+            &mut MaxSturdiness,
+            &mut Engines,
+            &mut Upgrades,
+        ),
+        With<Player>,
+    >,
+    mut text: Query<&mut Text, With<StationMenuText>>,
+) {
+    let economy = &config.economy;
+    let pressed = |key| !console.open && keyboard_input.just_pressed(key);
+    if pressed(KeyCode::Return) || pressed(KeyCode::Escape) {
+        *saved = false;
+        let _ = state.pop();
+        return;
+    }
+    let (mut cargo, mut fuel, mut sturdiness, mut max, mut engines, mut upgrades) =
+        match player.single_mut() {
+            Ok(player) => player,
+            Err(_) => return,
+        };
+
+    let fuel_capacity = hulls
+        .hulls
+        .get(selected.0)
+        .map_or(f32::INFINITY, |hull| hull.ship.fuel);
+    // Show the save confirmation until the next key press.
+    if keyboard_input.get_just_pressed().next().is_some() {
+        *saved = false;
+    }
+    if pressed(KeyCode::Key1) {
+        let earned: f32 = cargo
+            .ore
+            .drain()
+            .map(|(ore, amount)| amount * economy.ore_prices.get(&ore).copied().unwrap_or(0.0))
+            .sum();
+        credits.0 += earned.floor() as u32;
+    } else if pressed(KeyCode::Key2) {
+        fuel.0 += buy(&mut credits, fuel_capacity - fuel.0, economy.fuel_price);
+    } else if pressed(KeyCode::Key3) {
+        sturdiness.0 += buy(&mut credits, max.0 - sturdiness.0, economy.repair_price);
+    } else if pressed(KeyCode::Key7) {
+        if progression.record_score(score.0) {
+            info!("New best score: {}", score.0);
+        }
+        progression.save();
+        *saved = true;
+    } else {
+        let keys = [KeyCode::Key4, KeyCode::Key5, KeyCode::Key6];
+        for (key, upgrade) in keys.iter().zip(Upgrade::ALL.iter()) {
+            let price = upgrade_price(economy, &upgrades, *upgrade);
+            if !pressed(*key) || credits.0 < price {
+                continue;
+            }
+            credits.0 -= price;
+            *upgrades.0.entry(*upgrade).or_insert(0) += 1;
+            let factor = 1.0 + economy.upgrade_step;
+            match upgrade {
+                Upgrade::Engines => engines.thrust *= factor,
+                Upgrade::Hull => {
+                    sturdiness.0 += max.0 * economy.upgrade_step;
+                    max.0 *= factor;
+                }
+                Upgrade::Cargo => cargo.capacity *= factor,
+            }
+        }
+    }
+
+    let mut text = match text.single_mut() {
+        Ok(text) => text,
+        Err(_) => return,
+    };
+    let ore = Ore::ALL
+        .iter()
+        .filter_map(|ore| {
+            cargo
+                .ore
+                .get(ore)
+                .map(|amount| format!("{:.0} {}", amount, ore.name()))
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let upgrade_menu = Upgrade::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, upgrade)| {
+            format!(
+                "\n{}: upgrade {} to level {} ({} credits)",
+                i + 4,
+                upgrade.name(),
+                upgrades.level(*upgrade) + 1,
+                upgrade_price(economy, &upgrades, *upgrade)
+            )
+        })
+        .collect::<String>();
+    text.sections[0].value = format!(
+        "Station ({} credits)\n\n1: sell ore ({})\n2: refuel ({} credits per unit)\n3: repair ({} credits per point){}\n7: save progress\n\nenter: undock{}",
+        credits.0,
+        if ore.is_empty() { "hold empty".to_string() } else { ore },
+        economy.fuel_price,
+        economy.repair_price,
+        upgrade_menu,
+        if *saved { "\n\nprogress saved" } else { "" }
+    );
+}
+
+/// Close the station menu and resume the world.
+pub fn leave_station(
+    mut commands: Commands,
+    time: Res<Time>,
+    docking: Res<Docking>,
+    mut rapier: ResMut<RapierConfiguration>,
+    mut difficulty: ResMut<Difficulty>,
+    menu: Query<Entity, With<StationMenu>>,
+    mut spawned: Query<&mut SpawnedAt>,
+) {
+    rapier.physics_pipeline_active = true;
+    // Time stood still while docked, so don't let it count towards the run or anything's lifetime.
+    let docked_for = time.seconds_since_startup() - docking.docked_at;
+    difficulty.run_started += docked_for;
+    for mut spawned_at in spawned.iter_mut() {
+        spawned_at.0 += docked_for;
+    }
+    for entity in menu.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -1,4 +1,5 @@
 use bevy::math::{Vec2, Vec3};
+use itertools::Itertools;
 
 pub fn project2d(v: Vec3) -> Vec2 {
     Vec2::new(v.x, v.y)
@@ -7,3 +8,485 @@ pub fn project2d(v: Vec3) -> Vec2 {
 pub fn from_polar(r: f32, t: f32) -> Vec2 {
     Vec2::new(-t.sin(), t.cos()) * r
 }
+
+/// Rotate `v` counterclockwise by `angle` radians.
+pub fn rotate(v: Vec2, angle: f32) -> Vec2 {
+    let (sin, cos) = angle.sin_cos();
+    Vec2::new(v.x * cos - v.y * sin, v.x * sin + v.y * cos)
+}
+
+/// Area enclosed by a polygon, in either winding order.
+pub fn polygon_area(points: &[(f32, f32)]) -> f32 {
+    signed_double_area(points).abs() / 2.0
+}
+
+/// How far `b` turns left of the line from `o` through `a`: positive for a left turn, negative
+/// for a right turn and zero when the three points are in line.
+fn cross(o: (f32, f32), a: (f32, f32), b: (f32, f32)) -> f32 {
+    (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+}
+
+/// Twice the area of a polygon, positive when it winds counterclockwise.
+fn signed_double_area(points: &[(f32, f32)]) -> f32 {
+    points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|((x1, y1), (x2, y2))| x1 * y2 - x2 * y1)
+        .sum()
+}
+
+/// Whether a polygon turns the same way at every corner. Corners in a straight line don't count.
+pub fn is_convex(points: &[(f32, f32)]) -> bool {
+    let n = points.len();
+    let mut turn = 0.0;
+    for i in 0..n {
+        let c = cross(points[i], points[(i + 1) % n], points[(i + 2) % n]);
+        if c == 0.0 {
+            continue;
+        }
+        if turn == 0.0 {
+            turn = c.signum();
+        } else if c.signum() != turn {
+            return false;
+        }
+    }
+    true
+}
+
+/// Whether any two edges of a polygon cross, other than neighbours meeting at their corner.
+pub fn is_self_intersecting(points: &[(f32, f32)]) -> bool {
+    let n = points.len();
+    let edge = |i: usize| (points[i], points[(i + 1) % n]);
+    (0..n).any(|i| {
+        (i + 2..n).filter(|&j| !(i == 0 && j == n - 1)).any(|j| {
+            let ((a, b), (c, d)) = (edge(i), edge(j));
+            cross(c, d, a) * cross(c, d, b) < 0.0 && cross(a, b, c) * cross(a, b, d) < 0.0
+        })
+    })
+}
+
+/// Split a simple polygon into counterclockwise triangles of indices into `points`, by clipping
+/// off one ear at a time. Returns `None` if no ear can be found, either because the polygon isn't
+/// simple or because corners lie right on the lines between other corners.
+fn triangulate(points: &[(f32, f32)]) -> Option<Vec<Vec<usize>>> {
+    let mut remaining = (0..points.len()).collect::<Vec<_>>();
+    if signed_double_area(points) < 0.0 {
+        remaining.reverse();
+    }
+    let mut triangles = Vec::with_capacity(points.len().saturating_sub(2));
+    while remaining.len() > 3 {
+        let m = remaining.len();
+        let corner = |i: usize| {
+            (
+                remaining[(i + m - 1) % m],
+                remaining[i],
+                remaining[(i + 1) % m],
+            )
+        };
+        // An ear is a convex corner whose triangle has no other corner inside it or on its edges.
+        let ear = (0..m).find(|&i| {
+            let (a, b, c) = corner(i);
+            let (pa, pb, pc) = (points[a], points[b], points[c]);
+            cross(pa, pb, pc) > 0.0
+                && remaining.iter().all(|&k| {
+                    let p = points[k];
+                    k == a
+                        || k == b
+                        || k == c
+                        || cross(pa, pb, p) < 0.0
+                        || cross(pb, pc, p) < 0.0
+                        || cross(pc, pa, p) < 0.0
+                })
+        })?;
+        let (a, b, c) = corner(ear);
+        triangles.push(vec![a, b, c]);
+        remaining.remove(ear);
+    }
+    triangles.push(remaining);
+    Some(triangles)
+}
+
+/// Join two counterclockwise polygons of indices along an edge they share, or `None` if they
+/// don't share one.
+fn join_along_shared_edge(a: &[usize], b: &[usize]) -> Option<Vec<usize>> {
+    (0..a.len()).find_map(|i| {
+        let (p, q) = (a[i], a[(i + 1) % a.len()]);
+        // Both wind the same way, so `b` runs along the shared edge from `q` back to `p`.
+        let j = (0..b.len()).find(|&j| b[j] == q && b[(j + 1) % b.len()] == p)?;
+        let mut joined = (1..=a.len())
+            .map(|k| a[(i + k) % a.len()])
+            .collect::<Vec<_>>();
+        joined.extend((2..b.len()).map(|k| b[(j + k) % b.len()]));
+        Some(joined)
+    })
+}
+
+/// Split a simple polygon into convex pieces. It is cut into triangles first, which are then
+/// merged back together wherever the merged piece stays convex.
+/// Returns `None` if it can't be triangulated.
+pub fn convex_parts(points: &[(f32, f32)]) -> Option<Vec<Vec<(f32, f32)>>> {
+    // Corners in a straight line would make flat triangles, so leave them out.
+    let n = points.len();
+    let points = (0..n)
+        .filter(|&i| cross(points[(i + n - 1) % n], points[i], points[(i + 1) % n]) != 0.0)
+        .map(|i| points[i])
+        .collect::<Vec<_>>();
+    let outline = |part: &[usize]| part.iter().map(|&k| points[k]).collect::<Vec<_>>();
+
+    let mut parts = triangulate(&points)?;
+    let mut merged = true;
+    while merged {
+        merged = false;
+        'search: for i in 0..parts.len() {
+            for j in i + 1..parts.len() {
+                if let Some(joined) = join_along_shared_edge(&parts[i], &parts[j]) {
+                    if is_convex(&outline(&joined)) {
+                        parts[i] = joined;
+                        parts.swap_remove(j);
+                        merged = true;
+                        break 'search;
+                    }
+                }
+            }
+        }
+    }
+    Some(parts.iter().map(|part| outline(part)).collect())
+}
+
+/// Center of the area enclosed by a polygon, in either winding order.
+pub fn polygon_centroid(points: &[(f32, f32)]) -> Vec2 {
+    let double_area = signed_double_area(points);
+    if double_area == 0.0 {
+        let sum = points
+            .iter()
+            .fold(Vec2::ZERO, |sum, (x, y)| sum + Vec2::new(*x, *y));
+        return sum / points.len().max(1) as f32;
+    }
+    let sum = points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .fold(Vec2::ZERO, |sum, ((x1, y1), (x2, y2))| {
+            sum + Vec2::new(x1 + x2, y1 + y2) * (x1 * y2 - x2 * y1)
+        });
+    sum / (3.0 * double_area)
+}
+
+/// Cut a simple polygon along the line through `origin` in `direction`, and return the pieces
+/// either side of it. A concave polygon can fall into more than two pieces.
+pub fn split_polygon(points: &[(f32, f32)], origin: Vec2, direction: Vec2) -> Vec<Vec<(f32, f32)>> {
+    let side = |(x, y): (f32, f32)| direction.perp_dot(Vec2::new(x, y) - origin) >= 0.0;
+    // Along the line, in units of `direction`.
+    let along = |(x, y): (f32, f32)| direction.dot(Vec2::new(x, y) - origin);
+
+    // The outline with a point added wherever an edge crosses the line.
+    let mut outline = Vec::with_capacity(points.len() + 4);
+    let mut crossings = Vec::new();
+    for (i, a) in points.iter().enumerate() {
+        let b = points[(i + 1) % points.len()];
+        outline.push(*a);
+        if side(*a) != side(b) {
+            let (da, db) = (
+                direction.perp_dot(Vec2::new(a.0, a.1) - origin),
+                direction.perp_dot(Vec2::new(b.0, b.1) - origin),
+            );
+            let t = da / (da - db);
+            crossings.push(outline.len());
+            outline.push((a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t));
+        }
+    }
+    if crossings.is_empty() {
+        return vec![points.to_vec()];
+    }
+
+    // Sorted along the line, the crossings alternate between leaving and entering the polygon,
+    // so each pair of them bounds a stretch of the line that lies inside it.
+    crossings.sort_by(|a, b| along(outline[*a]).partial_cmp(&along(outline[*b])).unwrap());
+    let mut partner = vec![None; outline.len()];
+    for pair in crossings.chunks(2) {
+        if let [a, b] = *pair {
+            partner[a] = Some(b);
+            partner[b] = Some(a);
+        }
+    }
+
+    // Walk round the outline, taking the shortcut across the line at each crossing.
+    // Every piece has at least one corner of the original polygon, and each corner is in just one.
+    let n = outline.len();
+    let mut visited = vec![false; n];
+    let mut pieces = Vec::new();
+    for start in 0..n {
+        if visited[start] || partner[start].is_some() || crossings.contains(&start) {
+            continue;
+        }
+        let mut piece = Vec::new();
+        let mut current = start;
+        loop {
+            visited[current] = true;
+            piece.push(outline[current]);
+            current = match partner[current] {
+                Some(other) => {
+                    piece.push(outline[other]);
+                    (other + 1) % n
+                }
+                None => (current + 1) % n,
+            };
+            if current == start || piece.len() > 2 * n {
+                break;
+            }
+        }
+        piece.dedup();
+        pieces.push(piece);
+    }
+    pieces
+}
+
+/// Distance from the origin to the furthest point of an outline.
+pub fn outline_radius(points: &[(f32, f32)]) -> f32 {
+    points
+        .iter()
+        .map(|(x, y)| Vec2::new(*x, *y).length())
+        .fold(0.0, f32::max)
+}
+
+/// Seconds until a body at `offset` moving at `relative_velocity` comes within `radius` of the origin,
+/// or `None` if it never does or is already that close.
+pub fn time_to_impact(offset: Vec2, relative_velocity: Vec2, radius: f32) -> Option<f32> {
+    let a = relative_velocity.length_squared();
+    let b = 2.0 * offset.dot(relative_velocity);
+    let c = offset.length_squared() - radius * radius;
+    if a == 0.0 || c <= 0.0 {
+        return None;
+    }
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let t = (-b - discriminant.sqrt()) / (2.0 * a);
+    if t >= 0.0 {
+        Some(t)
+    } else {
+        None
+    }
+}
+
+/// Move `current` towards `target` like a critically damped spring, taking about `smooth_time`
+/// seconds to get there. `velocity` carries the spring's state between calls.
+pub fn smooth_damp(
+    current: f32,
+    target: f32,
+    velocity: &mut f32,
+    smooth_time: f32,
+    dt: f32,
+) -> f32 {
+    let omega = 2.0 / smooth_time;
+    let x = omega * dt;
+    // Cheap approximation of exp(-x).
+    let decay = 1.0 / (1.0 + x + 0.48 * x * x + 0.235 * x * x * x);
+    let change = current - target;
+    let temp = (*velocity + omega * change) * dt;
+    *velocity = (*velocity - omega * temp) * decay;
+    target + (change + temp) * decay
+}
+
+/// Mix a seed and a pair of grid coordinates into a well-scrambled 64-bit value.
+pub fn hash_coords(seed: u64, x: i32, y: i32) -> u64 {
+    let mut h = seed ^ (x as u32 as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    h ^= (y as u32 as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+    // Finalizer from splitmix64.
+    h = (h ^ (h >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    h = (h ^ (h >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    h ^ (h >> 31)
+}
+
+/// Map a hash to `0.0..1.0`, evenly.
+pub fn unit_hash(h: u64) -> f32 {
+    (h >> 40) as f32 / (1u64 << 24) as f32
+}
+
+/// Smooth noise in `0.0..1.0` that changes over distances of about 1.
+/// Values at whole coordinates are random; in between they are blended.
+pub fn value_noise(seed: u64, p: Vec2) -> f32 {
+    let cell = p.floor();
+    let (x, y) = (cell.x as i32, cell.y as i32);
+    let corner = |dx, dy| unit_hash(hash_coords(seed, x + dx, y + dy));
+    let t = p - cell;
+    // Smoothstep, so there are no creases along cell edges.
+    let t = t * t * (Vec2::splat(3.0) - 2.0 * t);
+    let lerp = |a: f32, b: f32, t: f32| a + (b - a) * t;
+    lerp(
+        lerp(corner(0, 0), corner(1, 0), t.x),
+        lerp(corner(0, 1), corner(1, 1), t.x),
+        t.y,
+    )
+}
+
+/// Split space into irregular cells about 1 across, and find the one containing `p`.
+/// Each whole grid square holds one randomly placed site, and every point belongs to the
+/// nearest site, so all cells cover the same area on average.
+pub fn nearest_site(seed: u64, p: Vec2) -> (i32, i32) {
+    let cell = p.floor();
+    let (x, y) = (cell.x as i32, cell.y as i32);
+    let site = |cx: i32, cy: i32| {
+        let h = hash_coords(seed, cx, cy);
+        Vec2::new(cx as f32, cy as f32) + Vec2::new(unit_hash(h), unit_hash(h.rotate_left(24)))
+    };
+    // A site two squares away can still be nearer than the one in p's own square.
+    (-2..=2)
+        .cartesian_product(-2..=2)
+        .map(|(dx, dy)| (x + dx, y + dy))
+        .min_by(|a, b| {
+            let da = site(a.0, a.1).distance_squared(p);
+            let db = site(b.0, b.1).distance_squared(p);
+            da.partial_cmp(&db).unwrap()
+        })
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: [(f32, f32); 4] = [(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)];
+    const L_SHAPE: [(f32, f32); 6] = [
+        (0.0, 0.0),
+        (2.0, 0.0),
+        (2.0, 1.0),
+        (1.0, 1.0),
+        (1.0, 2.0),
+        (0.0, 2.0),
+    ];
+    const U_SHAPE: [(f32, f32); 8] = [
+        (0.0, 0.0),
+        (3.0, 0.0),
+        (3.0, 3.0),
+        (2.0, 3.0),
+        (2.0, 1.0),
+        (1.0, 1.0),
+        (1.0, 3.0),
+        (0.0, 3.0),
+    ];
+    const BOW_TIE: [(f32, f32); 4] = [(0.0, 0.0), (2.0, 2.0), (2.0, 0.0), (0.0, 2.0)];
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-4, "{} is not {}", a, b);
+    }
+
+    fn total_area(pieces: &[Vec<(f32, f32)>]) -> f32 {
+        pieces.iter().map(|piece| polygon_area(piece)).sum()
+    }
+
+    fn reversed(points: &[(f32, f32)]) -> Vec<(f32, f32)> {
+        points.iter().rev().copied().collect()
+    }
+
+    #[test]
+    fn convexity() {
+        assert!(is_convex(&SQUARE));
+        assert!(is_convex(&reversed(&SQUARE)));
+        assert!(!is_convex(&L_SHAPE));
+        assert!(!is_convex(&U_SHAPE));
+    }
+
+    #[test]
+    fn self_intersection() {
+        assert!(!is_self_intersecting(&SQUARE));
+        assert!(!is_self_intersecting(&L_SHAPE));
+        assert!(!is_self_intersecting(&U_SHAPE));
+        assert!(is_self_intersecting(&BOW_TIE));
+    }
+
+    #[test]
+    fn triangles_cover_the_polygon_counterclockwise() {
+        for shape in [&L_SHAPE[..], &reversed(&L_SHAPE), &U_SHAPE[..]] {
+            let triangles = triangulate(shape).unwrap();
+            assert_eq!(triangles.len(), shape.len() - 2);
+            let triangles = triangles
+                .iter()
+                .map(|triangle| triangle.iter().map(|&i| shape[i]).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            for triangle in triangles.iter() {
+                assert!(signed_double_area(triangle) > 0.0);
+            }
+            assert_close(total_area(&triangles), polygon_area(shape));
+        }
+    }
+
+    #[test]
+    fn convex_polygon_stays_whole() {
+        let parts = convex_parts(&SQUARE).unwrap();
+        assert_eq!(parts.len(), 1);
+        assert_close(total_area(&parts), 4.0);
+    }
+
+    #[test]
+    fn concave_polygons_split_into_convex_parts() {
+        for (shape, count) in [(&L_SHAPE[..], 2), (&U_SHAPE[..], 3)] {
+            let parts = convex_parts(shape).unwrap();
+            assert_eq!(parts.len(), count);
+            assert!(parts.iter().all(|part| is_convex(part)));
+            assert_close(total_area(&parts), polygon_area(shape));
+        }
+    }
+
+    #[test]
+    fn collinear_points_are_dropped() {
+        let square = [(0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)];
+        assert!(is_convex(&square));
+        let parts = convex_parts(&square).unwrap();
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].len(), 4);
+        assert!(!parts[0].contains(&(1.0, 0.0)));
+        assert_close(polygon_area(&parts[0]), 4.0);
+    }
+
+    #[test]
+    fn centroid() {
+        assert_eq!(polygon_centroid(&SQUARE), Vec2::new(1.0, 1.0));
+        let expected = Vec2::new(5.0, 5.0) / 6.0;
+        for shape in [L_SHAPE.to_vec(), reversed(&L_SHAPE)] {
+            let centroid = polygon_centroid(&shape);
+            assert_close(centroid.x, expected.x);
+            assert_close(centroid.y, expected.y);
+        }
+    }
+
+    #[test]
+    fn split_square_in_half() {
+        let pieces = split_polygon(&SQUARE, Vec2::new(1.0, 1.0), Vec2::new(0.0, 1.0));
+        assert_eq!(pieces.len(), 2);
+        for piece in pieces.iter() {
+            assert_close(polygon_area(piece), 2.0);
+        }
+    }
+
+    #[test]
+    fn split_u_shape_across_both_arms() {
+        let pieces = split_polygon(&U_SHAPE, Vec2::new(0.0, 2.0), Vec2::new(1.0, 0.0));
+        assert_eq!(pieces.len(), 3);
+        assert_close(total_area(&pieces), polygon_area(&U_SHAPE));
+        let mut areas = pieces
+            .iter()
+            .map(|piece| polygon_area(piece))
+            .collect::<Vec<_>>();
+        areas.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        for (area, expected) in areas.iter().zip([1.0, 1.0, 5.0]) {
+            assert_close(*area, expected);
+        }
+    }
+
+    #[test]
+    fn split_through_corners() {
+        let diamond = [(0.0, -1.0), (1.0, 0.0), (0.0, 1.0), (-1.0, 0.0)];
+        let pieces = split_polygon(&diamond, Vec2::ZERO, Vec2::new(1.0, 0.0));
+        assert_eq!(pieces.len(), 2);
+        for piece in pieces.iter() {
+            assert_close(polygon_area(piece), 1.0);
+        }
+    }
+
+    #[test]
+    fn split_missing_the_polygon() {
+        let pieces = split_polygon(&SQUARE, Vec2::new(0.0, 5.0), Vec2::new(1.0, 0.0));
+        assert_eq!(pieces, vec![SQUARE.to_vec()]);
+    }
+}